@@ -1,11 +1,16 @@
 //! Contains everything related to sliding puzzle algorithms (sequences of moves on a puzzle).
 
 pub mod algorithm;
+pub mod annotated;
 pub mod as_slice;
 pub mod axis;
+pub mod compound;
+pub mod diff;
 pub mod direction;
 pub mod display;
+pub mod library;
 pub mod metric;
 pub mod r#move;
 pub mod moves;
+pub mod notation;
 pub mod slice;
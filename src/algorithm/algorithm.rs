@@ -22,11 +22,12 @@ use crate::{
             algorithm::{AlgorithmDisplay as _, DisplaySpaced, DisplayUnspaced},
             r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort},
         },
-        metric::Metric,
+        metric::{Metric, Mtm, SequenceMetric, Stm},
+        notation::Notation,
         r#move::r#move::Move,
         slice::AlgorithmSlice,
     },
-    puzzle::sliding_puzzle::SlidingPuzzle,
+    puzzle::{puzzle::Puzzle, size::Size, sliding_puzzle::SlidingPuzzle, symmetry::Symmetry},
 };
 
 /// Error type for [`Algorithm::try_slice`].
@@ -46,6 +47,11 @@ pub enum SliceError {
         /// The length of the [`Algorithm`].
         len: u64,
     },
+
+    /// A boundary of the range is within a move that can not be split into two moves with the
+    /// required lengths in the [`Metric`] used for slicing.
+    #[error("InvalidBoundary: position {0} is within a move that can not be split")]
+    InvalidBoundary(u64),
 }
 
 /// A sequence of moves.
@@ -73,11 +79,11 @@ impl Algorithm {
         &self.moves
     }
 
-    /// The length of the algorithm in the [`Metric`] `M`.
+    /// The length of the algorithm in the [`SequenceMetric`] `M`.
     #[must_use]
     pub fn len<M, T>(&self) -> T
     where
-        M: Metric,
+        M: SequenceMetric,
         T: PrimInt + Sum + 'static,
         u64: AsPrimitive<T>,
     {
@@ -206,6 +212,20 @@ impl Algorithm {
         }
     }
 
+    /// Returns the algorithm obtained by applying `symmetry` to every move.
+    ///
+    /// See [`Puzzle::apply_symmetry`] for carrying a solution over to a transformed puzzle.
+    #[must_use]
+    pub fn apply_symmetry(&self, symmetry: Symmetry) -> Self {
+        Self {
+            moves: self
+                .moves
+                .iter()
+                .map(|m| m.apply_symmetry(symmetry))
+                .collect(),
+        }
+    }
+
     /// Returns the algorithm obtained by concatenating `n` copies of `self`.
     #[must_use]
     pub fn repeat(&self, n: usize) -> Self {
@@ -215,18 +235,62 @@ impl Algorithm {
     }
 
     /// Returns an [`AlgorithmSlice`] containing the (single-tile) moves in the range `range`.
+    ///
+    /// Equivalent to `self.try_slice_by::<Stm>(range)`.
     pub fn try_slice(&self, range: Range<u64>) -> Result<AlgorithmSlice<'_>, SliceError> {
+        self.try_slice_by::<Stm>(range)
+    }
+
+    /// Returns an [`AlgorithmSlice`] containing the (multi-tile) moves in the range `range`.
+    ///
+    /// Equivalent to `self.try_slice_by::<Mtm>(range)`.
+    pub fn try_slice_mtm(&self, range: Range<u64>) -> Result<AlgorithmSlice<'_>, SliceError> {
+        self.try_slice_by::<Mtm>(range)
+    }
+
+    /// The length of the algorithm as measured when slicing in the [`Metric`] `M`, i.e. the sum of
+    /// [`Metric::len`] over all moves.
+    fn slice_len<M: Metric>(&self) -> u64 {
+        self.moves.iter().map(|&m| M::len::<u64>(m)).sum()
+    }
+
+    /// Returns a [`Move`] in direction `dir` with amount at most `max_amount` and length `len` in
+    /// the [`Metric`] `M`, or `None` if `len` is 0. Used to cut a move at a slice boundary.
+    fn partial_move<M: Metric>(
+        dir: Direction,
+        max_amount: u64,
+        len: u64,
+        position: u64,
+    ) -> Result<Option<Move>, SliceError> {
+        if len == 0 {
+            return Ok(None);
+        }
+
+        M::split_amount(Move::new(dir, max_amount), len)
+            .map(|amount| Some(Move::new(dir, amount)))
+            .ok_or(SliceError::InvalidBoundary(position))
+    }
+
+    /// Returns an [`AlgorithmSlice`] containing the moves in the range `range`, where positions
+    /// are measured in the [`Metric`] `M`.
+    ///
+    /// A move that is cut by the start or end of the range is split into two moves, e.g. in
+    /// [`Stm`], `D10LU10R[5..15]` is `D5LU4`.
+    pub fn try_slice_by<M: Metric>(
+        &self,
+        range: Range<u64>,
+    ) -> Result<AlgorithmSlice<'_>, SliceError> {
         if range.start > range.end {
             return Err(SliceError::UnorderedRange(range));
         }
 
-        let len = self.len_stm();
+        let len = self.slice_len::<M>();
         if range.start > len || range.end > len {
             return Err(SliceError::OutOfRange { range, len });
         }
 
-        let iter = iter::once(0).chain(self.moves.iter().scan(0, |a, b| {
-            *a += b.amount;
+        let iter = iter::once(0).chain(self.moves.iter().scan(0, |a, &b| {
+            *a += M::len::<u64>(b);
             Some(*a)
         }));
 
@@ -234,7 +298,7 @@ impl Algorithm {
         let (start_idx, start_total) = iter
             .clone()
             .find_position(|&i| i >= range.start)
-            .unwrap_or_else(|| (self.moves.len() + 1, self.len_stm()));
+            .unwrap_or((self.moves.len() + 1, len));
 
         // Find the last move where all moves up to and including this one have a combined length
         // <= range.end
@@ -242,20 +306,24 @@ impl Algorithm {
             .clone()
             .tuple_windows()
             .find_position(|&(_, j)| j > range.end)
-            .map_or_else(
-                || (self.moves.len(), self.len_stm()),
-                |(idx, (i, _))| (idx, i),
-            );
+            .map_or((self.moves.len(), len), |(idx, (i, _))| (idx, i));
 
         if start_idx > end_idx {
             // The beginning and the end of the slice are both within a single move, e.g. U9[3..7].
             // Return a slice containing a single move with direction = the direction of the move
-            // that we sliced through, and amount = length of the range.
+            // that we sliced through, and length = length of the range.
+            let first = match self.moves.get(start_idx - 1) {
+                Some(mv) => Self::partial_move::<M>(
+                    mv.direction,
+                    mv.amount,
+                    range.end - range.start,
+                    range.start,
+                )?,
+                None => None,
+            };
+
             Ok(AlgorithmSlice {
-                first: self
-                    .moves
-                    .get(start_idx - 1)
-                    .and_then(|mv| Move::new_nonzero(mv.direction, range.end - range.start).ok()),
+                first,
                 middle: &[],
                 last: None,
             })
@@ -263,39 +331,88 @@ impl Algorithm {
             // The middle section of the slice (everything except maybe the first and last moves)
             // is given by the slice of `self.moves` from `start_idx` to `end_idx`. The first and
             // last moves (if needed) are created by indexing into `self.moves` and creating moves
-            // with the relevant direction and amount.
+            // with the relevant direction and length.
+            let first = match start_idx.checked_sub(1).and_then(|idx| self.moves.get(idx)) {
+                Some(mv) => Self::partial_move::<M>(
+                    mv.direction,
+                    mv.amount,
+                    start_total - range.start,
+                    range.start,
+                )?,
+                None => None,
+            };
+            let last = match self.moves.get(end_idx) {
+                Some(mv) => Self::partial_move::<M>(
+                    mv.direction,
+                    mv.amount,
+                    range.end - end_total,
+                    range.end,
+                )?,
+                None => None,
+            };
+
             Ok(AlgorithmSlice {
-                first: start_idx
-                    .checked_sub(1)
-                    .and_then(|idx| self.moves.get(idx))
-                    .and_then(|mv| Move::new_nonzero(mv.direction, start_total - range.start).ok()),
+                first,
                 middle: &self.moves[start_idx..end_idx],
-                last: self
-                    .moves
-                    .get(end_idx)
-                    .and_then(|mv| Move::new_nonzero(mv.direction, range.end - end_total).ok()),
+                last,
             })
         }
     }
 
-    /// Returns an [`AlgorithmSlice`] containing the (multi-tile) moves in the range `range`.
-    pub fn try_slice_mtm(&self, range: Range<u64>) -> Result<AlgorithmSlice<'_>, SliceError> {
-        if range.start > range.end {
-            return Err(SliceError::UnorderedRange(range));
-        }
-
-        let len = self.len_mtm();
-        if range.start > len || range.end > len {
-            return Err(SliceError::OutOfRange { range, len });
-        }
+    /// Splits the algorithm into two [`AlgorithmSlice`]s at position `mid`, measured in the
+    /// [`Metric`] `M`. See [`Algorithm::try_slice_by`].
+    pub fn split_at<M: Metric>(
+        &self,
+        mid: u64,
+    ) -> Result<(AlgorithmSlice<'_>, AlgorithmSlice<'_>), SliceError> {
+        let len = self.slice_len::<M>();
+        Ok((
+            self.try_slice_by::<M>(0..mid)?,
+            self.try_slice_by::<M>(mid..len)?,
+        ))
+    }
 
-        Ok(AlgorithmSlice {
-            first: None,
-            middle: &self.moves[range.start as usize..range.end as usize],
-            last: None,
+    /// An iterator over consecutive [`AlgorithmSlice`]s of length `n` in the [`Metric`] `M`. The
+    /// last slice is shorter if the length of the algorithm is not a multiple of `n`.
+    ///
+    /// An item is an error if one of its boundaries is within a move that can not be split in
+    /// `M`, which never happens for [`Stm`] and [`Mtm`].
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn chunks<M: Metric>(
+        &self,
+        n: u64,
+    ) -> impl Iterator<Item = Result<AlgorithmSlice<'_>, SliceError>> + '_ {
+        assert!(n != 0, "chunk size must be non-zero");
+
+        let len = self.slice_len::<M>();
+        (0..len.div_ceil(n)).map(move |i| {
+            let start = i * n;
+            self.try_slice_by::<M>(start..(start + n).min(len))
         })
     }
 
+    /// An iterator over all [`AlgorithmSlice`]s of length `n` in the [`Metric`] `M`, in order of
+    /// their start position. There are no windows if the algorithm is shorter than `n`.
+    ///
+    /// An item is an error if one of its boundaries is within a move that can not be split in
+    /// `M`, which never happens for [`Stm`] and [`Mtm`].
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn windows<M: Metric>(
+        &self,
+        n: u64,
+    ) -> impl Iterator<Item = Result<AlgorithmSlice<'_>, SliceError>> + '_ {
+        assert!(n != 0, "window size must be non-zero");
+
+        let count = (self.slice_len::<M>() + 1).saturating_sub(n);
+        (0..count).map(move |start| self.try_slice_by::<M>(start..start + n))
+    }
+
     /// Checks if `self` is a solution of `puzzle`.
     #[must_use]
     pub fn is_solution_of<Puzzle>(&self, mut puzzle: Puzzle) -> bool
@@ -306,6 +423,80 @@ impl Algorithm {
         b && puzzle.is_solved()
     }
 
+    /// Returns the permutation obtained by applying the algorithm to a puzzle of size `size` with
+    /// the gap starting at `gap_start`, or `None` if `gap_start` is out of bounds or the algorithm
+    /// can not be applied.
+    ///
+    /// The returned vector contains, for each position on the puzzle, the position that the piece
+    /// now at that position started at.
+    #[must_use]
+    pub fn effect(&self, size: Size, gap_start: (u64, u64)) -> Option<Vec<u64>> {
+        if !size.is_within_bounds(gap_start) {
+            return None;
+        }
+
+        let mut puzzle = Puzzle::new(size);
+        let (x, y) = gap_start;
+        puzzle.swap_piece_with_gap(x + size.width() * y);
+
+        // Position of each piece before applying the algorithm
+        let mut start = vec![0; size.area() as usize];
+        for (i, &piece) in puzzle.pieces().iter().enumerate() {
+            start[piece as usize] = i as u64;
+        }
+
+        puzzle
+            .try_apply_alg(self)
+            .then(|| puzzle.pieces().iter().map(|&p| start[p as usize]).collect())
+    }
+
+    /// Checks if `self` and `other` have the same effect on a puzzle of size `size`, for every
+    /// starting position of the gap.
+    ///
+    /// Two algorithms are only equivalent if they can be applied from the same gap positions. See
+    /// [`Algorithm::effect`].
+    #[must_use]
+    pub fn is_equivalent(&self, other: &Self, size: Size) -> bool {
+        let (w, h) = size.into();
+        (0..h)
+            .cartesian_product(0..w)
+            .all(|(y, x)| self.effect(size, (x, y)) == other.effect(size, (x, y)))
+    }
+
+    /// Returns the canonical form of the algorithm, which can be used to deduplicate a collection
+    /// of algorithms.
+    ///
+    /// No two moves along different axes commute, so every segment between changes of axis is
+    /// already in a unique order, and the canonical form is the [`Algorithm::simplified`] form.
+    ///
+    /// Algorithms with equal canonical forms have the same effect from every gap position where
+    /// both can be applied, but they are not necessarily equivalent (see
+    /// [`Algorithm::is_equivalent`]), e.g. `RUDR` can not be applied everywhere that `R2` can.
+    /// Conversely, equivalent algorithms do not always have equal canonical forms, e.g. an
+    /// algorithm that cycles three pieces three times. Use [`Algorithm::effects`] to deduplicate
+    /// by equivalence instead.
+    #[must_use]
+    pub fn canonical(&self) -> Self {
+        self.simplified()
+    }
+
+    /// Returns the [`Algorithm::effect`] of the algorithm from every starting position of the
+    /// gap on a puzzle of size `size`, in row-major order of the gap positions.
+    ///
+    /// Two algorithms have equal effects if and only if they are equivalent (see
+    /// [`Algorithm::is_equivalent`]), so this can be used as a key to deduplicate a collection of
+    /// algorithms, e.g. in a [`HashSet`].
+    ///
+    /// [`HashSet`]: std::collections::HashSet
+    #[must_use]
+    pub fn effects(&self, size: Size) -> Vec<Option<Vec<u64>>> {
+        let (w, h) = size.into();
+        (0..h)
+            .cartesian_product(0..w)
+            .map(|(y, x)| self.effect(size, (x, y)))
+            .collect()
+    }
+
     /// Helper function for creating a [`DisplaySpaced<DisplayLongSpaced>`] around `self`.
     #[must_use]
     pub fn display_long_spaced(&self) -> DisplaySpaced<'_, DisplayLongSpaced> {
@@ -358,68 +549,10 @@ pub enum ParseAlgorithmError {
 impl FromStr for Algorithm {
     type Err = ParseAlgorithmError;
 
+    /// Parses an algorithm written in the standard notation, e.g. "U2 R D L3" or "U2RDL3". See
+    /// [`Notation::parse_algorithm`] for other notations.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut alg = Self::default();
-
-        let mut dir = None;
-        let mut amount = None;
-
-        for c in s.chars() {
-            // Direction character is the start of a new move
-            if let Ok(d) = Direction::try_from(c) {
-                // Push the previous move, if there was one
-                if let Some(dir) = dir {
-                    alg.push(Move::new(dir, amount.unwrap_or(1)));
-                }
-
-                // Set the new direction and default amount for the next move
-                dir = Some(d);
-                amount = None;
-            }
-            // The number after a move
-            else if let Some(d) = c.to_digit(10) {
-                let d = d as u64;
-
-                // Must have a direction before an amount
-                if dir.is_none() {
-                    return Err(ParseAlgorithmError::MissingDirection);
-                }
-
-                // Append the next digit to the number
-                if let Some(a) = amount {
-                    amount = Some(
-                        a.checked_mul(10)
-                            .ok_or(ParseAlgorithmError::Overflow)?
-                            .checked_add(d)
-                            .ok_or(ParseAlgorithmError::Overflow)?,
-                    );
-                } else {
-                    amount = Some(d);
-                }
-            }
-            // A whitespace character signals the end of a move
-            else if c.is_whitespace() {
-                // Push the previous move, if there was one
-                if let Some(dir) = dir {
-                    alg.push(Move::new(dir, amount.unwrap_or(1)));
-                }
-
-                // Direction and amount for the next move are unknown
-                dir = None;
-                amount = None;
-            }
-            // Any other character is invalid
-            else {
-                return Err(ParseAlgorithmError::InvalidCharacter(c));
-            }
-        }
-
-        // Push the last move
-        if let Some(dir) = dir {
-            alg.push(Move::new(dir, amount.unwrap_or(1)));
-        }
-
-        Ok(alg)
+        Notation::standard().parse_algorithm(s)
     }
 }
 
@@ -687,6 +820,69 @@ mod tests {
         assert_eq!(a.transpose(), b);
     }
 
+    #[test]
+    fn test_effect() {
+        let size = Size::new(2, 2).unwrap();
+        let a = Algorithm::from_str("DRUL").unwrap();
+        assert_eq!(a.effect(size, (1, 1)), Some(vec![2, 0, 1, 3]));
+        assert_eq!(a.repeat(3).effect(size, (1, 1)), Some(vec![0, 1, 2, 3]));
+        assert_eq!(a.effect(size, (0, 0)), None);
+        assert_eq!(a.effect(size, (2, 0)), None);
+    }
+
+    #[test]
+    fn test_is_equivalent() {
+        let size = Size::new(2, 2).unwrap();
+        let a = Algorithm::from_str("DRUL").unwrap();
+        let b = Algorithm::from_str("RDLU").unwrap();
+        assert!(!a.is_equivalent(&b, size));
+        assert!(a.repeat(3).is_equivalent(&b.repeat(3), size));
+
+        let size = Size::new(4, 4).unwrap();
+        let a = Algorithm::from_str("R R").unwrap();
+        let b = Algorithm::from_str("R2").unwrap();
+        assert!(a.is_equivalent(&b, size));
+        assert!(!a.is_equivalent(&Algorithm::from_str("R").unwrap(), size));
+        assert!(!b.is_equivalent(&Algorithm::from_str("RUDR").unwrap(), size));
+    }
+
+    #[test]
+    fn test_canonical() {
+        let a = Algorithm::from_str("R R U D2 L D").unwrap();
+        let b = Algorithm::from_str("R2 D L D").unwrap();
+        assert_eq!(a.canonical(), b);
+        assert_eq!(a.canonical(), b.canonical());
+    }
+
+    #[test]
+    fn test_effects() {
+        let size = Size::new(4, 4).unwrap();
+        let effects =
+            ["R R", "R2", "RUDR", "R", "R2"].map(|a| Algorithm::from_str(a).unwrap().effects(size));
+        assert_eq!(effects[0], effects[1]);
+        assert_ne!(effects[1], effects[2]);
+
+        let unique = effects
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn test_apply_symmetry() {
+        let a = Algorithm::from_str("D2RUR2D2L3URU").unwrap();
+        assert_eq!(a.apply_symmetry(Symmetry::ReflectDiagonal), a.transpose());
+        assert_eq!(
+            a.apply_symmetry(Symmetry::RotateCw),
+            Algorithm::from_str("L2DRD2L2U3RDR").unwrap()
+        );
+        assert_eq!(
+            a.apply_symmetry(Symmetry::RotateCw)
+                .apply_symmetry(Symmetry::RotateCcw),
+            a
+        );
+    }
+
     #[test]
     fn test_repeat() {
         let a = Algorithm::from_str("U2LD3R").unwrap();
@@ -793,6 +989,123 @@ mod tests {
             );
             assert_eq!(alg.try_slice_mtm(2..9), slice!("", "LU10RUR2D2D3", ""));
         }
+
+        #[test]
+        fn test_slice_by() {
+            let alg = Algorithm::from_str("R2DLU10RUR2D2D3D5L5U2L").unwrap();
+
+            assert_eq!(alg.try_slice_by::<Stm>(11..30), alg.try_slice(11..30));
+            assert_eq!(alg.try_slice_by::<Mtm>(2..9), alg.try_slice_mtm(2..9));
+            assert_eq!(
+                alg.try_slice_by::<Mtm>(0..14),
+                Err(SliceError::OutOfRange {
+                    range: 0..14,
+                    len: 13
+                })
+            );
+        }
+
+        #[test]
+        fn test_split_at() {
+            let alg = Algorithm::from_str("R2DLU10R").unwrap();
+
+            let (a, b) = alg.split_at::<Stm>(6).unwrap();
+            assert_eq!(Algorithm::from(a), Algorithm::from_str("R2DLU2").unwrap());
+            assert_eq!(Algorithm::from(b), Algorithm::from_str("U8R").unwrap());
+
+            let (a, b) = alg.split_at::<Mtm>(2).unwrap();
+            assert_eq!(Algorithm::from(a), Algorithm::from_str("R2D").unwrap());
+            assert_eq!(Algorithm::from(b), Algorithm::from_str("LU10R").unwrap());
+
+            assert!(alg.split_at::<Mtm>(6).is_err());
+        }
+
+        #[test]
+        fn test_chunks() {
+            let alg = Algorithm::from_str("R2DLU10R").unwrap();
+
+            let chunks = alg
+                .chunks::<Stm>(4)
+                .map(|c| c.map(Algorithm::from))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(
+                chunks,
+                ["R2DL", "U4", "U4", "U2R"]
+                    .map(|s| Algorithm::from_str(s).unwrap())
+                    .to_vec()
+            );
+
+            let chunks = alg
+                .chunks::<Mtm>(2)
+                .map(|c| c.map(Algorithm::from))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(
+                chunks,
+                ["R2D", "LU10", "R"]
+                    .map(|s| Algorithm::from_str(s).unwrap())
+                    .to_vec()
+            );
+
+            assert_eq!(Algorithm::new().chunks::<Stm>(3).count(), 0);
+        }
+
+        #[test]
+        fn test_windows() {
+            let alg = Algorithm::from_str("R2DLU3").unwrap();
+
+            let windows = alg
+                .windows::<Stm>(6)
+                .map(|w| w.map(Algorithm::from))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(
+                windows,
+                ["R2DLU2", "RDLU3"]
+                    .map(|s| Algorithm::from_str(s).unwrap())
+                    .to_vec()
+            );
+
+            let windows = alg
+                .windows::<Mtm>(3)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(windows.len(), 2);
+            assert!(windows.iter().all(|w| w.len_mtm::<u64>() == 3));
+
+            assert_eq!(alg.windows::<Mtm>(5).count(), 0);
+        }
+
+        #[test]
+        fn test_chunks_invalid_boundary() {
+            // Metric where every tile moved counts twice, so odd positions are within a move
+            struct Double;
+
+            impl Metric for Double {
+                const HAS_MOVECOUNT_PARITY: bool = true;
+
+                fn len<T>(mv: Move) -> T
+                where
+                    T: PrimInt + 'static,
+                    u64: AsPrimitive<T>,
+                {
+                    (2 * mv.amount()).as_()
+                }
+            }
+
+            let alg = Algorithm::from_str("R2DL").unwrap();
+
+            let chunks = alg.chunks::<Double>(4).collect::<Vec<_>>();
+            assert_eq!(chunks.len(), 2);
+            assert!(chunks.iter().all(Result::is_ok));
+
+            let chunks = alg.chunks::<Double>(3).collect::<Vec<_>>();
+            assert_eq!(chunks.len(), 3);
+            assert_eq!(chunks[0], Err(SliceError::InvalidBoundary(3)));
+            assert_eq!(chunks[1], Err(SliceError::InvalidBoundary(3)));
+            assert!(chunks[2].is_ok());
+        }
     }
 
     mod from_str {
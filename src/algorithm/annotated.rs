@@ -0,0 +1,258 @@
+//! Defines the [`AnnotatedAlgorithm`] type, an algorithm split into steps with optional comments.
+
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::algorithm::{
+    algorithm::{Algorithm, ParseAlgorithmError},
+    slice::AlgorithmSlice,
+};
+
+/// Error type for [`AnnotatedAlgorithm::from_str`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParseAnnotatedAlgorithmError {
+    /// Returned when the moves on a line could not be parsed.
+    #[error("ParseAlgorithmError: failed to parse line {line}: {error}")]
+    ParseAlgorithmError {
+        /// The line number, starting from 0.
+        line: usize,
+        /// The error returned when parsing the line.
+        error: ParseAlgorithmError,
+    },
+}
+
+/// A step of an [`AnnotatedAlgorithm`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Step {
+    range: Range<u64>,
+    comment: Option<String>,
+}
+
+impl Step {
+    /// The range of single tile moves in the full algorithm that belong to this step.
+    #[must_use]
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// The comment attached to this step, if there is one.
+    #[must_use]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+/// An [`Algorithm`] split into steps, where each step can have a comment attached to it.
+///
+/// When parsed from a string, each non-empty line is one step, and anything after `//` on a line
+/// is a comment, e.g.
+///
+/// ```text
+/// R2 D L U3 // first row
+/// D2 R U    // second row
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnnotatedAlgorithm {
+    algorithm: Algorithm,
+    steps: Vec<Step>,
+}
+
+impl AnnotatedAlgorithm {
+    /// Creates a new empty [`AnnotatedAlgorithm`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a step to the end of the algorithm.
+    pub fn push_step(&mut self, alg: &Algorithm, comment: Option<String>) {
+        let start = self.algorithm.len_stm();
+        for &m in alg.moves() {
+            self.algorithm.push(m);
+        }
+        let end = self.algorithm.len_stm();
+
+        self.steps.push(Step {
+            range: start..end,
+            comment,
+        });
+    }
+
+    /// The full algorithm, with all steps concatenated.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        &self.algorithm
+    }
+
+    /// Extracts the full algorithm, consuming `self`.
+    #[must_use]
+    pub fn into_algorithm(self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// The steps of the algorithm.
+    #[must_use]
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Returns the moves of step `idx` as an [`AlgorithmSlice`] of the full algorithm, or `None`
+    /// if there is no step `idx`.
+    #[must_use]
+    pub fn step_slice(&self, idx: usize) -> Option<AlgorithmSlice<'_>> {
+        let step = self.steps.get(idx)?;
+        self.algorithm.try_slice(step.range()).ok()
+    }
+
+    /// An iterator over the steps, together with their moves as [`AlgorithmSlice`]s.
+    pub fn step_slices(&self) -> impl Iterator<Item = (&Step, AlgorithmSlice<'_>)> {
+        self.steps.iter().map(|step| {
+            // Ranges of steps are always within the bounds of the algorithm
+            (step, self.algorithm.try_slice(step.range()).unwrap())
+        })
+    }
+}
+
+impl From<AnnotatedAlgorithm> for Algorithm {
+    fn from(value: AnnotatedAlgorithm) -> Self {
+        value.into_algorithm()
+    }
+}
+
+impl From<Algorithm> for AnnotatedAlgorithm {
+    /// Creates an [`AnnotatedAlgorithm`] with a single step containing all of the moves.
+    fn from(value: Algorithm) -> Self {
+        let mut alg = Self::new();
+        alg.push_step(&value, None);
+        alg
+    }
+}
+
+impl Display for AnnotatedAlgorithm {
+    /// Formats the algorithm with one step per line, and comments after `//`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (step, slice)) in self.step_slices().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let moves = Algorithm::from(slice);
+            let moves = moves.display_short_spaced().to_string();
+
+            match (moves.is_empty(), step.comment()) {
+                (_, None) => write!(f, "{moves}")?,
+                (true, Some(comment)) => write!(f, "// {comment}")?,
+                (false, Some(comment)) => write!(f, "{moves} // {comment}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for AnnotatedAlgorithm {
+    type Err = ParseAnnotatedAlgorithmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut alg = Self::new();
+
+        for (line_number, line) in s.lines().enumerate() {
+            let (moves, comment) = match line.split_once("//") {
+                Some((moves, comment)) => (moves, Some(comment.trim().to_owned())),
+                None => (line, None),
+            };
+
+            let moves = Algorithm::from_str(moves).map_err(|error| {
+                ParseAnnotatedAlgorithmError::ParseAlgorithmError {
+                    line: line_number,
+                    error,
+                }
+            })?;
+
+            // Skip empty lines
+            if moves.is_empty() && comment.is_none() {
+                continue;
+            }
+
+            alg.push_step(&moves, comment);
+        }
+
+        Ok(alg)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for AnnotatedAlgorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AnnotatedAlgorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let alg_str = String::deserialize(deserializer)?;
+        Self::from_str(&alg_str).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALG: &str = "R2 D L U3 // first row\n\n  D2R U\n// done";
+
+    #[test]
+    fn test_from_str() {
+        let alg = AnnotatedAlgorithm::from_str(ALG).unwrap();
+        assert_eq!(alg.algorithm(), &Algorithm::from_str("R2DLU3D2RU").unwrap());
+
+        let steps = alg.steps();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].range(), 0..7);
+        assert_eq!(steps[0].comment(), Some("first row"));
+        assert_eq!(steps[1].range(), 7..11);
+        assert_eq!(steps[1].comment(), None);
+        assert_eq!(steps[2].range(), 11..11);
+        assert_eq!(steps[2].comment(), Some("done"));
+    }
+
+    #[test]
+    fn test_step_slice() {
+        let alg = AnnotatedAlgorithm::from_str(ALG).unwrap();
+        assert_eq!(
+            Algorithm::from(alg.step_slice(1).unwrap()),
+            Algorithm::from_str("D2RU").unwrap()
+        );
+        assert!(alg.step_slice(3).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let alg = AnnotatedAlgorithm::from_str(ALG).unwrap();
+        assert_eq!(alg.to_string(), "R2 D L U3 // first row\nD2 R U\n// done");
+        assert_eq!(AnnotatedAlgorithm::from_str(&alg.to_string()).unwrap(), alg);
+    }
+
+    #[test]
+    fn test_from_str_error() {
+        assert_eq!(
+            AnnotatedAlgorithm::from_str("R2 D\nL / U"),
+            Err(ParseAnnotatedAlgorithmError::ParseAlgorithmError {
+                line: 1,
+                error: ParseAlgorithmError::InvalidCharacter('/'),
+            })
+        );
+    }
+}
@@ -0,0 +1,577 @@
+//! Defines the [`CompoundAlgorithm`] type, an algorithm containing groups, repetitions,
+//! conjugates, and commutators.
+
+use std::{fmt::Display, iter::Peekable, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::algorithm::{algorithm::Algorithm, direction::Direction, r#move::r#move::Move};
+
+/// A single element of a [`CompoundAlgorithm`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Node {
+    /// A single move, e.g. `R2`.
+    Move(Move),
+
+    /// A repeated group of moves, e.g. `(RDLU)3`.
+    Group {
+        /// The moves inside the group.
+        body: CompoundAlgorithm,
+        /// The number of times the group is repeated.
+        repeat: u64,
+    },
+
+    /// A conjugate `[A: B]`, equal to `A B A'`, where `A'` is the inverse of `A`.
+    Conjugate {
+        /// The setup moves `A`.
+        setup: CompoundAlgorithm,
+        /// The moves `B`.
+        body: CompoundAlgorithm,
+        /// The number of times the conjugate is repeated.
+        repeat: u64,
+    },
+
+    /// A commutator `[A, B]`, equal to `A B A' B'`, where `A'` and `B'` are the inverses of `A`
+    /// and `B`.
+    Commutator {
+        /// The moves `A`.
+        a: CompoundAlgorithm,
+        /// The moves `B`.
+        b: CompoundAlgorithm,
+        /// The number of times the commutator is repeated.
+        repeat: u64,
+    },
+}
+
+impl Node {
+    /// Expands the node into an [`Algorithm`].
+    ///
+    /// Returns an error if the expanded algorithm would contain more than [`MAX_EXPANDED_LEN`]
+    /// moves.
+    pub fn expand(&self) -> Result<Algorithm, ExpandCompoundAlgorithmError> {
+        match self.expanded_len() {
+            Some(len) if len <= MAX_EXPANDED_LEN => Ok(self.expand_unchecked()),
+            _ => Err(ExpandCompoundAlgorithmError::TooLong),
+        }
+    }
+
+    /// The number of moves in the expanded node, or `None` if it overflows a `u64`.
+    fn expanded_len(&self) -> Option<u64> {
+        match self {
+            Self::Move(_) => Some(1),
+            Self::Group { body, repeat } => body.expanded_len()?.checked_mul(*repeat),
+            Self::Conjugate {
+                setup,
+                body,
+                repeat,
+            } => setup
+                .expanded_len()?
+                .checked_mul(2)?
+                .checked_add(body.expanded_len()?)?
+                .checked_mul(*repeat),
+            Self::Commutator { a, b, repeat } => a
+                .expanded_len()?
+                .checked_add(b.expanded_len()?)?
+                .checked_mul(2)?
+                .checked_mul(*repeat),
+        }
+    }
+
+    fn expand_unchecked(&self) -> Algorithm {
+        let (alg, repeat) = match self {
+            Self::Move(mv) => return Algorithm::with_moves(vec![*mv]),
+            Self::Group { body, repeat } => (body.expand_unchecked(), *repeat),
+            Self::Conjugate {
+                setup,
+                body,
+                repeat,
+            } => {
+                let setup = setup.expand_unchecked();
+                let inverse = setup.inverse();
+                (setup + body.expand_unchecked() + inverse, *repeat)
+            }
+            Self::Commutator { a, b, repeat } => {
+                let (a, b) = (a.expand_unchecked(), b.expand_unchecked());
+                let (a_inv, b_inv) = (a.inverse(), b.inverse());
+                (a + b + a_inv + b_inv, *repeat)
+            }
+        };
+
+        alg.repeat(repeat as usize)
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let repeat = match self {
+            Self::Move(mv) => return mv.fmt(f),
+            Self::Group { body, repeat } => {
+                write!(f, "({body})")?;
+                *repeat
+            }
+            Self::Conjugate {
+                setup,
+                body,
+                repeat,
+            } => {
+                write!(f, "[{setup}: {body}]")?;
+                *repeat
+            }
+            Self::Commutator { a, b, repeat } => {
+                write!(f, "[{a}, {b}]")?;
+                *repeat
+            }
+        };
+
+        if repeat == 1 {
+            Ok(())
+        } else {
+            write!(f, "{repeat}")
+        }
+    }
+}
+
+/// An algorithm that may contain repeated groups like `(RDLU)3`, conjugates like `[R2: DLU]`, and
+/// commutators like `[U, R]`. These can be nested, e.g. `[(RD)2: [U, L]]`.
+///
+/// A [`CompoundAlgorithm`] can be expanded into an [`Algorithm`] using
+/// [`CompoundAlgorithm::expand`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CompoundAlgorithm {
+    nodes: Vec<Node>,
+}
+
+impl CompoundAlgorithm {
+    /// Creates a new empty [`CompoundAlgorithm`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`CompoundAlgorithm`] from a list of [`Node`]s.
+    #[must_use]
+    pub fn with_nodes(nodes: Vec<Node>) -> Self {
+        Self { nodes }
+    }
+
+    /// The nodes of the algorithm.
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Appends a node to the end of the algorithm.
+    pub fn push(&mut self, node: Node) {
+        self.nodes.push(node);
+    }
+
+    /// Checks if the algorithm is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Expands the algorithm into an [`Algorithm`], by expanding all groups, conjugates, and
+    /// commutators.
+    ///
+    /// Returns an error if the expanded algorithm would contain more than [`MAX_EXPANDED_LEN`]
+    /// moves.
+    pub fn expand(&self) -> Result<Algorithm, ExpandCompoundAlgorithmError> {
+        match self.expanded_len() {
+            Some(len) if len <= MAX_EXPANDED_LEN => Ok(self.expand_unchecked()),
+            _ => Err(ExpandCompoundAlgorithmError::TooLong),
+        }
+    }
+
+    /// The number of moves in the expanded algorithm, or `None` if it overflows a `u64`.
+    fn expanded_len(&self) -> Option<u64> {
+        self.nodes
+            .iter()
+            .try_fold(0u64, |len, node| len.checked_add(node.expanded_len()?))
+    }
+
+    fn expand_unchecked(&self) -> Algorithm {
+        let mut alg = Algorithm::new();
+        for node in &self.nodes {
+            alg += node.expand_unchecked();
+        }
+        alg
+    }
+}
+
+impl Display for CompoundAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for node in &self.nodes {
+            node.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Algorithm> for CompoundAlgorithm {
+    fn from(value: Algorithm) -> Self {
+        Self::with_nodes(value.moves.into_iter().map(Node::Move).collect())
+    }
+}
+
+impl TryFrom<&CompoundAlgorithm> for Algorithm {
+    type Error = ExpandCompoundAlgorithmError;
+
+    fn try_from(value: &CompoundAlgorithm) -> Result<Self, Self::Error> {
+        value.expand()
+    }
+}
+
+/// The maximum number of moves in an expanded [`CompoundAlgorithm`].
+pub const MAX_EXPANDED_LEN: u64 = 1 << 20;
+
+/// The maximum depth of nested brackets in a [`CompoundAlgorithm`] read with
+/// [`CompoundAlgorithm::from_str`].
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// Error type for [`CompoundAlgorithm::expand`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExpandCompoundAlgorithmError {
+    /// Returned when the expanded algorithm would contain more than [`MAX_EXPANDED_LEN`] moves,
+    /// e.g. "(R)99999999999".
+    #[error("TooLong: the expanded algorithm would contain more than {MAX_EXPANDED_LEN} moves")]
+    TooLong,
+}
+
+/// Error type for [`CompoundAlgorithm::from_str`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParseCompoundAlgorithmError {
+    /// Found a character that can not appear at this point in the algorithm, e.g. "U2 a D" or
+    /// "(U2 R]".
+    #[error("InvalidCharacter: character {0} is invalid")]
+    InvalidCharacter(char),
+
+    /// Read a number with no direction or group before it, e.g. "U2 R3 5 D".
+    #[error("MissingDirection: a number must be preceded by a direction or a group")]
+    MissingDirection,
+
+    /// Overflow when reading a number.
+    #[error("Overflow: integer overflow occurred when reading a number")]
+    Overflow,
+
+    /// Reached the end of the string before a bracket was closed, e.g. "(U2 R".
+    #[error("UnclosedBracket: bracket {0} was not closed")]
+    UnclosedBracket(char),
+
+    /// Found a square bracket with no `:` or `,` separator, e.g. "[U2 R]".
+    #[error("MissingSeparator: square brackets must contain a `:` or `,` separator")]
+    MissingSeparator,
+
+    /// Brackets are nested more than [`MAX_NESTING_DEPTH`] levels deep, e.g. "((((R))))" with
+    /// more brackets.
+    #[error("TooDeep: brackets can not be nested more than {MAX_NESTING_DEPTH} levels deep")]
+    TooDeep,
+}
+
+struct Parser<I: Iterator<Item = char>> {
+    chars: Peekable<I>,
+    depth: usize,
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn number(&mut self) -> Result<Option<u64>, ParseCompoundAlgorithmError> {
+        let mut n: Option<u64> = None;
+
+        while let Some(d) = self.chars.peek().and_then(|c| c.to_digit(10)) {
+            self.chars.next();
+            n = Some(
+                n.unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(u64::from(d)))
+                    .ok_or(ParseCompoundAlgorithmError::Overflow)?,
+            );
+        }
+
+        Ok(n)
+    }
+
+    /// Parses a sequence of nodes inside a bracket, one level deeper than the current sequence.
+    fn nested_sequence(&mut self) -> Result<CompoundAlgorithm, ParseCompoundAlgorithmError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(ParseCompoundAlgorithmError::TooDeep);
+        }
+
+        self.depth += 1;
+        let alg = self.sequence();
+        self.depth -= 1;
+        alg
+    }
+
+    /// Parses a sequence of nodes, stopping before a closing bracket or separator, or at the end
+    /// of the string.
+    fn sequence(&mut self) -> Result<CompoundAlgorithm, ParseCompoundAlgorithmError> {
+        let mut alg = CompoundAlgorithm::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let Some(&c) = self.chars.peek() else {
+                return Ok(alg);
+            };
+
+            if let Ok(dir) = Direction::try_from(c) {
+                self.chars.next();
+                let amount = self.number()?.unwrap_or(1);
+                alg.push(Node::Move(Move::new(dir, amount)));
+            } else if c == '(' {
+                self.chars.next();
+                let body = self.nested_sequence()?;
+                self.close(')')?;
+                let repeat = self.number()?.unwrap_or(1);
+                alg.push(Node::Group { body, repeat });
+            } else if c == '[' {
+                self.chars.next();
+                let first = self.nested_sequence()?;
+                let separator = match self.chars.next() {
+                    Some(c @ (':' | ',')) => c,
+                    Some(']') => return Err(ParseCompoundAlgorithmError::MissingSeparator),
+                    Some(c) => return Err(ParseCompoundAlgorithmError::InvalidCharacter(c)),
+                    None => return Err(ParseCompoundAlgorithmError::UnclosedBracket('[')),
+                };
+                let second = self.nested_sequence()?;
+                self.close(']')?;
+                let repeat = self.number()?.unwrap_or(1);
+
+                alg.push(if separator == ':' {
+                    Node::Conjugate {
+                        setup: first,
+                        body: second,
+                        repeat,
+                    }
+                } else {
+                    Node::Commutator {
+                        a: first,
+                        b: second,
+                        repeat,
+                    }
+                });
+            } else if c.is_ascii_digit() {
+                return Err(ParseCompoundAlgorithmError::MissingDirection);
+            } else if matches!(c, ')' | ']' | ':' | ',') {
+                return Ok(alg);
+            } else {
+                return Err(ParseCompoundAlgorithmError::InvalidCharacter(c));
+            }
+        }
+    }
+
+    fn close(&mut self, bracket: char) -> Result<(), ParseCompoundAlgorithmError> {
+        match self.chars.next() {
+            Some(c) if c == bracket => Ok(()),
+            Some(c) => Err(ParseCompoundAlgorithmError::InvalidCharacter(c)),
+            None => Err(ParseCompoundAlgorithmError::UnclosedBracket(
+                match bracket {
+                    ')' => '(',
+                    _ => '[',
+                },
+            )),
+        }
+    }
+}
+
+impl FromStr for CompoundAlgorithm {
+    type Err = ParseCompoundAlgorithmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().peekable(),
+            depth: 0,
+        };
+
+        let alg = parser.sequence()?;
+
+        // `sequence` only stops early at a closing bracket or separator, which is invalid here
+        parser.chars.next().map_or(Ok(alg), |c| {
+            Err(ParseCompoundAlgorithmError::InvalidCharacter(c))
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CompoundAlgorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CompoundAlgorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let alg_str = String::deserialize(deserializer)?;
+        Self::from_str(&alg_str).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(s: &str) -> Algorithm {
+        CompoundAlgorithm::from_str(s).unwrap().expand().unwrap()
+    }
+
+    #[test]
+    fn test_flat() {
+        assert_eq!(expand("R2 D L3U"), Algorithm::from_str("R2DL3U").unwrap());
+        assert_eq!(expand(""), Algorithm::new());
+    }
+
+    #[test]
+    fn test_group() {
+        assert_eq!(
+            expand("(RDLU)3"),
+            Algorithm::from_str("RDLU").unwrap().repeat(3)
+        );
+        assert_eq!(expand("(RDLU)"), Algorithm::from_str("RDLU").unwrap());
+        assert_eq!(expand("U(R)0D"), Algorithm::from_str("UD").unwrap());
+    }
+
+    #[test]
+    fn test_conjugate() {
+        assert_eq!(expand("[R2: DLU]"), Algorithm::from_str("R2DLUL2").unwrap());
+        assert_eq!(expand("[R: D]2"), Algorithm::from_str("RDLRDL").unwrap());
+    }
+
+    #[test]
+    fn test_commutator() {
+        assert_eq!(expand("[U, R]"), Algorithm::from_str("URDL").unwrap());
+        assert_eq!(
+            expand("[U2 R, L]"),
+            Algorithm::from_str("U2RLLD2R").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        assert_eq!(
+            expand("D [(RD)2: [U, L]] R"),
+            Algorithm::from_str("DRDRDULDRULULR").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for s in [
+            "(RDLU)3",
+            "[R2: DLU]",
+            "[U, R]2",
+            "D[(RD)2: [U, L]]R",
+            "URDL",
+        ] {
+            assert_eq!(CompoundAlgorithm::from_str(s).unwrap().to_string(), s);
+        }
+        assert_eq!(
+            CompoundAlgorithm::from_str("( R D )2 [ U,R ]")
+                .unwrap()
+                .to_string(),
+            "(RD)2[U, R]"
+        );
+    }
+
+    #[test]
+    fn test_from_algorithm() {
+        let alg = Algorithm::from_str("R3DLU2").unwrap();
+        let compound = CompoundAlgorithm::from(alg.clone());
+        assert_eq!(compound.nodes().len(), 4);
+        assert_eq!(compound.expand(), Ok(alg));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            CompoundAlgorithm::from_str("(RD"),
+            Err(ParseCompoundAlgorithmError::UnclosedBracket('('))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("[R: D"),
+            Err(ParseCompoundAlgorithmError::UnclosedBracket('['))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("[RD]"),
+            Err(ParseCompoundAlgorithmError::MissingSeparator)
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("(RD]"),
+            Err(ParseCompoundAlgorithmError::InvalidCharacter(']'))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("RD)"),
+            Err(ParseCompoundAlgorithmError::InvalidCharacter(')'))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("R: D"),
+            Err(ParseCompoundAlgorithmError::InvalidCharacter(':'))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("[R: D: L]"),
+            Err(ParseCompoundAlgorithmError::InvalidCharacter(':'))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("R 3"),
+            Err(ParseCompoundAlgorithmError::MissingDirection)
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("Ra"),
+            Err(ParseCompoundAlgorithmError::InvalidCharacter('a'))
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str("R99999999999999999999"),
+            Err(ParseCompoundAlgorithmError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+        let nested = |depth: usize| format!("{}R{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert_eq!(
+            expand(&nested(MAX_NESTING_DEPTH)),
+            Algorithm::from_str("R").unwrap()
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str(&nested(MAX_NESTING_DEPTH + 1)),
+            Err(ParseCompoundAlgorithmError::TooDeep)
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str(&nested(100_000)),
+            Err(ParseCompoundAlgorithmError::TooDeep)
+        );
+        assert_eq!(
+            CompoundAlgorithm::from_str(&"[R: ".repeat(100_000)),
+            Err(ParseCompoundAlgorithmError::TooDeep)
+        );
+    }
+
+    #[test]
+    fn test_expand_too_long() {
+        for s in [
+            "(R)99999999999",
+            "((R)99999999999)99999999999",
+            "[(R)1024: (D)1024]1024",
+        ] {
+            assert_eq!(
+                CompoundAlgorithm::from_str(s).unwrap().expand(),
+                Err(ExpandCompoundAlgorithmError::TooLong)
+            );
+        }
+    }
+}
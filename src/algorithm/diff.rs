@@ -0,0 +1,288 @@
+//! Defines the [`AlgorithmDiff`] type, a single tile move alignment of two algorithms.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::{
+    algorithm::Algorithm, as_slice::AsAlgorithmSlice, direction::Direction, r#move::r#move::Move,
+};
+
+/// One part of an [`AlgorithmDiff`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiffOp {
+    /// Moves that appear in both algorithms.
+    Common(Algorithm),
+    /// Moves that only appear in the new algorithm.
+    Inserted(Algorithm),
+    /// Moves that only appear in the old algorithm.
+    Deleted(Algorithm),
+}
+
+impl DiffOp {
+    /// The moves of this part of the diff.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        match self {
+            Self::Common(alg) | Self::Inserted(alg) | Self::Deleted(alg) => alg,
+        }
+    }
+}
+
+/// The difference between two algorithms, computed as an alignment of their single tile moves
+/// (see [`AlgorithmSlice::single_tile_moves`]) with the minimum number of insertions and
+/// deletions.
+///
+/// When an insertion and a deletion are adjacent, the deletion always comes first.
+///
+/// The [`Display`] implementation writes common moves as normal, deleted moves as `[-...-]` and
+/// inserted moves as `{+...+}`.
+///
+/// # Example
+///
+/// ```
+/// # use std::str::FromStr as _;
+/// # use slidy::algorithm::{algorithm::Algorithm, diff::AlgorithmDiff};
+/// let old = Algorithm::from_str("R2 D L U")?;
+/// let new = Algorithm::from_str("R2 U L U")?;
+/// let diff = AlgorithmDiff::new(&old, &new);
+/// assert_eq!(diff.distance(), 2);
+/// assert_eq!(diff.to_string(), "R2 [-D-] {+U+} L U");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`AlgorithmSlice::single_tile_moves`]: ../slice/struct.AlgorithmSlice.html#method.single_tile_moves
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AlgorithmDiff {
+    ops: Vec<DiffOp>,
+}
+
+impl AlgorithmDiff {
+    /// Computes the diff from `old` to `new`.
+    #[must_use]
+    pub fn new<'a, A, B>(old: &'a A, new: &'a B) -> Self
+    where
+        A: AsAlgorithmSlice<'a>,
+        B: AsAlgorithmSlice<'a>,
+    {
+        let a = old.as_slice().single_tile_moves().collect::<Vec<_>>();
+        let b = new.as_slice().single_tile_moves().collect::<Vec<_>>();
+        let (n, m) = (a.len(), b.len());
+
+        // lcs[i * (m + 1) + j] is the length of the longest common subsequence of a[i..] and b[j..]
+        let mut lcs = vec![0usize; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * (m + 1) + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+                };
+            }
+        }
+
+        let mut diff = Self::default();
+        let mut deleted = Algorithm::new();
+        let mut inserted = Algorithm::new();
+        let (mut i, mut j) = (0, 0);
+
+        loop {
+            match (a.get(i), b.get(j)) {
+                (Some(&x), Some(&y)) if x == y => {
+                    diff.flush(&mut deleted, &mut inserted);
+                    diff.push_common(x);
+                    i += 1;
+                    j += 1;
+                }
+                (Some(&x), Some(_)) if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] => {
+                    deleted.push_combine(Move::from(x));
+                    i += 1;
+                }
+                (Some(&x), None) => {
+                    deleted.push_combine(Move::from(x));
+                    i += 1;
+                }
+                (_, Some(&y)) => {
+                    inserted.push_combine(Move::from(y));
+                    j += 1;
+                }
+                (None, None) => break,
+            }
+        }
+        diff.flush(&mut deleted, &mut inserted);
+
+        diff
+    }
+
+    fn push_common(&mut self, dir: Direction) {
+        if let Some(DiffOp::Common(alg)) = self.ops.last_mut() {
+            alg.push_combine(Move::from(dir));
+        } else {
+            self.ops
+                .push(DiffOp::Common(Algorithm::with_moves(vec![Move::from(dir)])));
+        }
+    }
+
+    fn flush(&mut self, deleted: &mut Algorithm, inserted: &mut Algorithm) {
+        if !deleted.is_empty() {
+            self.ops.push(DiffOp::Deleted(std::mem::take(deleted)));
+        }
+        if !inserted.is_empty() {
+            self.ops.push(DiffOp::Inserted(std::mem::take(inserted)));
+        }
+    }
+
+    /// The parts of the diff, in order.
+    #[must_use]
+    pub fn ops(&self) -> &[DiffOp] {
+        &self.ops
+    }
+
+    /// The number of single tile moves that appear in both algorithms.
+    #[must_use]
+    pub fn common_len(&self) -> u64 {
+        self.ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Common(alg) => Some(alg.len_stm::<u64>()),
+                DiffOp::Inserted(_) | DiffOp::Deleted(_) => None,
+            })
+            .sum()
+    }
+
+    /// The number of single tile moves that need to be inserted or deleted to turn the old
+    /// algorithm into the new one.
+    #[must_use]
+    pub fn distance(&self) -> u64 {
+        self.ops
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Inserted(alg) | DiffOp::Deleted(alg) => Some(alg.len_stm::<u64>()),
+                DiffOp::Common(_) => None,
+            })
+            .sum()
+    }
+
+    /// Checks if the two algorithms have the same single tile moves.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, DiffOp::Common(_)))
+    }
+
+    /// Returns the old algorithm.
+    #[must_use]
+    pub fn old_algorithm(&self) -> Algorithm {
+        let mut alg = Algorithm::new();
+        for op in &self.ops {
+            if let DiffOp::Common(a) | DiffOp::Deleted(a) = op {
+                for &m in a.moves() {
+                    alg.push_combine(m);
+                }
+            }
+        }
+        alg
+    }
+
+    /// Returns the new algorithm.
+    #[must_use]
+    pub fn new_algorithm(&self) -> Algorithm {
+        let mut alg = Algorithm::new();
+        for op in &self.ops {
+            if let DiffOp::Common(a) | DiffOp::Inserted(a) = op {
+                for &m in a.moves() {
+                    alg.push_combine(m);
+                }
+            }
+        }
+        alg
+    }
+}
+
+impl Display for AlgorithmDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, op) in self.ops.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            let moves = op.algorithm().display_short_spaced();
+            match op {
+                DiffOp::Common(_) => write!(f, "{moves}")?,
+                DiffOp::Inserted(_) => write!(f, "{{+{moves}+}}")?,
+                DiffOp::Deleted(_) => write!(f, "[-{moves}-]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    fn diff(old: &str, new: &str) -> AlgorithmDiff {
+        let old = Algorithm::from_str(old).unwrap();
+        let new = Algorithm::from_str(new).unwrap();
+        AlgorithmDiff::new(&old, &new)
+    }
+
+    #[test]
+    fn test_identical() {
+        let d = diff("R2DLU", "RRDLU");
+        assert!(d.is_identical());
+        assert_eq!(d.distance(), 0);
+        assert_eq!(d.common_len(), 5);
+        assert_eq!(d.to_string(), "R2 D L U");
+    }
+
+    #[test]
+    fn test_empty() {
+        let d = diff("", "");
+        assert!(d.ops().is_empty());
+        assert_eq!(d.to_string(), "");
+
+        let d = diff("", "UL");
+        assert_eq!(
+            d.ops(),
+            &[DiffOp::Inserted(Algorithm::from_str("UL").unwrap())]
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let d = diff("R3 D L2 U", "R2 D L2 D U R");
+        assert_eq!(d.distance(), 3);
+        assert_eq!(d.common_len(), 6);
+        assert_eq!(d.to_string(), "R2 [-R-] D L2 {+D+} U {+R+}");
+    }
+
+    #[test]
+    fn test_replace() {
+        let d = diff("U2 L D", "U2 R U D");
+        assert_eq!(d.distance(), 3);
+        assert_eq!(
+            d.ops(),
+            &[
+                DiffOp::Common(Algorithm::from_str("U2").unwrap()),
+                DiffOp::Deleted(Algorithm::from_str("L").unwrap()),
+                DiffOp::Inserted(Algorithm::from_str("RU").unwrap()),
+                DiffOp::Common(Algorithm::from_str("D").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_old_new() {
+        let old = Algorithm::from_str("R3 D L2 U").unwrap();
+        let new = Algorithm::from_str("R2 D L2 D U R").unwrap();
+        let d = AlgorithmDiff::new(&old, &new);
+        assert_eq!(d.old_algorithm(), old);
+        assert_eq!(d.new_algorithm(), new);
+    }
+}
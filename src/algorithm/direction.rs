@@ -13,7 +13,7 @@ use rand::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::algorithm::axis::Axis;
+use crate::{algorithm::axis::Axis, puzzle::symmetry::Symmetry};
 
 /// The directions in which a piece can be moved.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -74,6 +74,31 @@ impl Direction {
         }
     }
 
+    /// The direction obtained by applying `symmetry` to the puzzle.
+    #[must_use]
+    pub fn apply_symmetry(&self, symmetry: Symmetry) -> Self {
+        match symmetry {
+            Symmetry::Id => *self,
+            Symmetry::RotateCw => match self {
+                Self::Up => Self::Right,
+                Self::Left => Self::Up,
+                Self::Down => Self::Left,
+                Self::Right => Self::Down,
+            },
+            Symmetry::RotateCcw => match self {
+                Self::Up => Self::Left,
+                Self::Left => Self::Down,
+                Self::Down => Self::Right,
+                Self::Right => Self::Up,
+            },
+            Symmetry::RotateHalf => self.inverse(),
+            Symmetry::ReflectVertical => self.reflect_up_down(),
+            Symmetry::ReflectHorizontal => self.reflect_left_right(),
+            Symmetry::ReflectDiagonal => self.transpose(),
+            Symmetry::ReflectAntidiagonal => self.transpose().inverse(),
+        }
+    }
+
     /// Returns the [`Axis`] along which this [`Direction`] moves.
     #[must_use]
     pub fn axis(&self) -> Axis {
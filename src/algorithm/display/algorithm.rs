@@ -8,7 +8,8 @@ use std::{
 };
 
 use crate::algorithm::{
-    as_slice::AsAlgorithmSlice, display::r#move::MoveDisplay, slice::AlgorithmSlice,
+    as_slice::AsAlgorithmSlice, display::r#move::MoveDisplay, notation::Notation,
+    slice::AlgorithmSlice,
 };
 
 /// Marker trait for structs that are used to display algorithms.
@@ -27,9 +28,22 @@ macro_rules! define_display {
             #[derive(Clone, Debug)]
             pub struct $name<'a, T: MoveDisplay + Display> {
                 algorithm: AlgorithmSlice<'a>,
+                notation: Notation,
                 phantom_t: PhantomData<T>,
             }
 
+            impl<T> $name<'_, T>
+            where
+                T: MoveDisplay + Display,
+            {
+                /// Sets the [`Notation`] used to write the moves.
+                #[must_use]
+                pub fn with_notation(mut self, notation: Notation) -> Self {
+                    self.notation = notation;
+                    self
+                }
+            }
+
             impl<'a, T> AlgorithmDisplay<'a> for $name<'a, T>
             where
                 T: MoveDisplay + Display,
@@ -40,6 +54,7 @@ macro_rules! define_display {
                 {
                     Self {
                         algorithm: algorithm.as_slice(),
+                        notation: Notation::standard(),
                         phantom_t: PhantomData,
                     }
                 }
@@ -60,12 +75,12 @@ impl<T: MoveDisplay + Display> Display for DisplaySpaced<'_, T> {
         let mut moves = self.algorithm.moves();
 
         if let Some(first) = moves.next() {
-            f.write_str(&T::new(first).to_string())?;
+            T::new(first).fmt_with_notation(&self.notation, f)?;
         }
 
         for m in moves {
             f.write_char(' ')?;
-            f.write_str(&T::new(m).to_string())?;
+            T::new(m).fmt_with_notation(&self.notation, f)?;
         }
 
         Ok(())
@@ -75,7 +90,7 @@ impl<T: MoveDisplay + Display> Display for DisplaySpaced<'_, T> {
 impl<T: MoveDisplay + Display> Display for DisplayUnspaced<'_, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for m in self.algorithm.moves() {
-            T::new(m).fmt(f)?;
+            T::new(m).fmt_with_notation(&self.notation, f)?;
         }
         Ok(())
     }
@@ -91,6 +106,7 @@ mod tests {
             algorithm::{AlgorithmDisplay as _, DisplaySpaced, DisplayUnspaced},
             r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort},
         },
+        notation::Notation,
     };
 
     #[test]
@@ -114,6 +130,21 @@ mod tests {
         assert_eq!(a.display_short_spaced().to_string(), d3);
         assert_eq!(a.display_short_unspaced().to_string(), d6);
     }
+
+    #[test]
+    fn test_display_notation() {
+        let a = Algorithm::from_str("U2RDL3").unwrap();
+        let n = Notation::lowercase().gap_relative(true);
+        let d1 = DisplaySpaced::<DisplayShort>::new(&a)
+            .with_notation(n)
+            .to_string();
+        let d2 = DisplayUnspaced::<DisplayLongUnspaced>::new(&a)
+            .with_notation(Notation::arrows())
+            .to_string();
+        assert_eq!(d1, "d2 l u r3");
+        assert_eq!(d2, "↑↑→↓←←←");
+        assert_eq!(n.parse_algorithm(&d1), Ok(a));
+    }
 }
 
 #[cfg(all(feature = "nightly", test))]
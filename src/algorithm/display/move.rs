@@ -2,13 +2,28 @@
 
 use std::fmt::Display;
 
-use crate::algorithm::r#move::r#move::Move;
+use crate::algorithm::{notation::Notation, r#move::r#move::Move};
 
-/// Marker trait for structs that are used to display moves.
+/// Trait for structs that are used to display moves.
 pub trait MoveDisplay {
     /// Create a new [`MoveDisplay`] for displaying `mv`.
     #[must_use]
     fn new(mv: Move) -> Self;
+
+    /// Formats the move using the characters of `notation`.
+    ///
+    /// The default implementation ignores `notation` and formats the move using [`Display`].
+    fn fmt_with_notation(
+        &self,
+        notation: &Notation,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result
+    where
+        Self: Display,
+    {
+        let _ = notation;
+        Display::fmt(self, f)
+    }
 }
 
 macro_rules! define_display {
@@ -18,9 +33,9 @@ macro_rules! define_display {
             #[derive(Clone, Debug)]
             pub struct $name(Move);
 
-            impl MoveDisplay for $name {
-                fn new(mv: Move) -> Self {
-                    Self(mv)
+            impl Display for $name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    self.fmt_with_notation(&Notation::standard(), f)
                 }
             }
         )*
@@ -75,9 +90,17 @@ define_display!(
     DisplayShort
 );
 
-impl Display for DisplayLongSpaced {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut s = self.0.direction.to_string();
+impl MoveDisplay for DisplayLongSpaced {
+    fn new(mv: Move) -> Self {
+        Self(mv)
+    }
+
+    fn fmt_with_notation(
+        &self,
+        notation: &Notation,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let mut s = notation.as_char(self.0.direction).to_string();
         s.push(' ');
         s = s.repeat(self.0.amount as usize);
         s.pop();
@@ -86,27 +109,56 @@ impl Display for DisplayLongSpaced {
     }
 }
 
-impl Display for DisplayLongUnspaced {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0.direction.to_string().repeat(self.0.amount as usize))
+impl MoveDisplay for DisplayLongUnspaced {
+    fn new(mv: Move) -> Self {
+        Self(mv)
+    }
+
+    fn fmt_with_notation(
+        &self,
+        notation: &Notation,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.write_str(
+            &notation
+                .as_char(self.0.direction)
+                .to_string()
+                .repeat(self.0.amount as usize),
+        )
     }
 }
 
-impl Display for DisplayShort {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl MoveDisplay for DisplayShort {
+    fn new(mv: Move) -> Self {
+        Self(mv)
+    }
+
+    fn fmt_with_notation(
+        &self,
+        notation: &Notation,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let c = notation.as_char(self.0.direction);
         if self.0.amount == 1 {
-            write!(f, "{}", self.0.direction)
+            write!(f, "{c}")
         } else {
-            write!(f, "{}{}", self.0.direction, self.0.amount)
+            write!(f, "{c}{}", self.0.amount)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr as _;
+
     use crate::algorithm::{
+        algorithm::Algorithm,
         direction::Direction,
-        display::r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort},
+        display::{
+            algorithm::{AlgorithmDisplay as _, DisplaySpaced},
+            r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort, MoveDisplay},
+        },
+        notation::Notation,
         r#move::r#move::Move,
     };
 
@@ -131,4 +183,47 @@ mod tests {
         assert_eq!(DisplayLongUnspaced(m).to_string(), "UUU");
         assert_eq!(DisplayShort(m).to_string(), "U3");
     }
+
+    #[test]
+    fn test_display_notation() {
+        struct WithNotation<T: MoveDisplay + std::fmt::Display>(T, Notation);
+
+        impl<T: MoveDisplay + std::fmt::Display> std::fmt::Display for WithNotation<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt_with_notation(&self.1, f)
+            }
+        }
+
+        let m = Move {
+            direction: Direction::Up,
+            amount: 3,
+        };
+        let n = Notation::arrows().gap_relative(true);
+        assert_eq!(WithNotation(DisplayLongSpaced(m), n).to_string(), "↓ ↓ ↓");
+        assert_eq!(WithNotation(DisplayLongUnspaced(m), n).to_string(), "↓↓↓");
+        assert_eq!(WithNotation(DisplayShort(m), n).to_string(), "↓3");
+    }
+
+    #[test]
+    fn test_default_fmt_with_notation() {
+        struct Amount(Move);
+
+        impl MoveDisplay for Amount {
+            fn new(mv: Move) -> Self {
+                Self(mv)
+            }
+        }
+
+        impl std::fmt::Display for Amount {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0.amount)
+            }
+        }
+
+        let a = Algorithm::from_str("U2RDL3").unwrap();
+        let d = DisplaySpaced::<Amount>::new(&a)
+            .with_notation(Notation::arrows())
+            .to_string();
+        assert_eq!(d, "2 1 1 3");
+    }
 }
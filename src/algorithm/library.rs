@@ -0,0 +1,222 @@
+//! Defines the [`AlgorithmLibrary`] type, a collection of algorithms indexed by the state that
+//! they solve.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    algorithm::{
+        algorithm::Algorithm,
+        metric::{Mtm, SequenceMetric, Stm},
+    },
+    puzzle::{puzzle::Puzzle, size::Size, sliding_puzzle::SlidingPuzzle as _},
+};
+
+/// Error type for [`AlgorithmLibrary`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LibraryError {
+    /// Returned when an algorithm can not be applied to the solved state of the library's size.
+    #[error("IllegalAlgorithm: algorithm {0} can not be applied to the solved state")]
+    IllegalAlgorithm(Algorithm),
+}
+
+/// A collection of algorithms for puzzles of a fixed [`Size`], indexed by the state that each
+/// algorithm solves.
+///
+/// An algorithm `a` solves the state obtained by applying `a.inverse()` to the solved puzzle, so
+/// every algorithm in the library must be applicable to the solved puzzle in reverse.
+///
+/// When serialized, only the size and the list of algorithms are stored, and the index is rebuilt
+/// when deserializing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "AlgorithmLibraryUnvalidated")
+)]
+pub struct AlgorithmLibrary {
+    size: Size,
+    algorithms: Vec<Algorithm>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    index: HashMap<Puzzle, Vec<usize>>,
+}
+
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+struct AlgorithmLibraryUnvalidated {
+    size: Size,
+    algorithms: Vec<Algorithm>,
+}
+
+impl TryFrom<AlgorithmLibraryUnvalidated> for AlgorithmLibrary {
+    type Error = LibraryError;
+
+    fn try_from(value: AlgorithmLibraryUnvalidated) -> Result<Self, Self::Error> {
+        let AlgorithmLibraryUnvalidated { size, algorithms } = value;
+
+        let mut library = Self::new(size);
+        for alg in algorithms {
+            library.insert(alg)?;
+        }
+
+        Ok(library)
+    }
+}
+
+impl AlgorithmLibrary {
+    /// Creates a new empty [`AlgorithmLibrary`] for puzzles of size `size`.
+    #[must_use]
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            algorithms: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// The size of the puzzles that the library is for.
+    #[must_use]
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// All algorithms in the library, in the order they were inserted.
+    #[must_use]
+    pub fn algorithms(&self) -> &[Algorithm] {
+        &self.algorithms
+    }
+
+    /// The number of algorithms in the library.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.algorithms.len()
+    }
+
+    /// Checks if the library is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.algorithms.is_empty()
+    }
+
+    /// The number of distinct states that are solved by algorithms in the library.
+    #[must_use]
+    pub fn num_cases(&self) -> usize {
+        self.index.len()
+    }
+
+    /// The state solved by `alg`, or `None` if `alg.inverse()` can not be applied to the solved
+    /// puzzle.
+    #[must_use]
+    pub fn case(&self, alg: &Algorithm) -> Option<Puzzle> {
+        let mut puzzle = Puzzle::new(self.size);
+        puzzle.try_apply_alg(&alg.inverse()).then_some(puzzle)
+    }
+
+    /// Adds an algorithm to the library.
+    ///
+    /// Returns `Ok(false)` if the algorithm was already in the library, in which case it is not
+    /// added again.
+    pub fn insert(&mut self, alg: Algorithm) -> Result<bool, LibraryError> {
+        let Some(case) = self.case(&alg) else {
+            return Err(LibraryError::IllegalAlgorithm(alg));
+        };
+
+        let indices = self.index.entry(case).or_default();
+        if indices.iter().any(|&i| self.algorithms[i] == alg) {
+            return Ok(false);
+        }
+
+        indices.push(self.algorithms.len());
+        self.algorithms.push(alg);
+
+        Ok(true)
+    }
+
+    /// Returns all algorithms in the library that solve `puzzle`, sorted by their length in the
+    /// metric `M`. Algorithms of equal length are in the order they were inserted.
+    #[must_use]
+    pub fn solutions<M: SequenceMetric>(&self, puzzle: &Puzzle) -> Vec<&Algorithm> {
+        let mut solutions = self.index.get(puzzle).map_or_else(Vec::new, |indices| {
+            indices.iter().map(|&i| &self.algorithms[i]).collect()
+        });
+
+        solutions.sort_by_key(|alg| alg.len::<M, u64>());
+        solutions
+    }
+
+    /// Equivalent to `self.solutions::<Stm>(puzzle)`.
+    #[must_use]
+    pub fn solutions_stm(&self, puzzle: &Puzzle) -> Vec<&Algorithm> {
+        self.solutions::<Stm>(puzzle)
+    }
+
+    /// Equivalent to `self.solutions::<Mtm>(puzzle)`.
+    #[must_use]
+    pub fn solutions_mtm(&self, puzzle: &Puzzle) -> Vec<&Algorithm> {
+        self.solutions::<Mtm>(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    fn library() -> AlgorithmLibrary {
+        let mut library = AlgorithmLibrary::new(Size::new(3, 2).unwrap());
+        for alg in ["RDLU", "DRUL", "RDLURDLURDLU", "RDLURDLURDLURDLU", "LU"] {
+            library.insert(Algorithm::from_str(alg).unwrap()).unwrap();
+        }
+        library
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut library = library();
+        assert_eq!(library.len(), 5);
+        assert_eq!(
+            library.insert(Algorithm::from_str("RDLU").unwrap()),
+            Ok(false)
+        );
+        assert_eq!(
+            library.insert(Algorithm::from_str("D").unwrap()),
+            Err(LibraryError::IllegalAlgorithm(
+                Algorithm::from_str("D").unwrap()
+            ))
+        );
+        assert_eq!(library.len(), 5);
+    }
+
+    #[test]
+    fn test_solutions() {
+        let library = library();
+
+        let puzzle = Puzzle::from_str("1 2 3/4 0 5").unwrap();
+        assert!(library.solutions_stm(&puzzle).is_empty());
+
+        let puzzle = library.case(&Algorithm::from_str("RDLU").unwrap()).unwrap();
+        let solutions = library.solutions_stm(&puzzle);
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(solutions[0], &Algorithm::from_str("RDLU").unwrap());
+        assert_eq!(
+            solutions[1],
+            &Algorithm::from_str("RDLURDLURDLURDLU").unwrap()
+        );
+        for alg in solutions {
+            assert!(alg.is_solution_of(puzzle.clone()));
+        }
+
+        let solutions = library.solutions_mtm(&puzzle);
+        assert_eq!(solutions[0], &Algorithm::from_str("RDLU").unwrap());
+    }
+
+    #[test]
+    fn test_num_cases() {
+        let library = library();
+        assert_eq!(library.num_cases(), 4);
+    }
+}
@@ -1,5 +1,7 @@
-//! Defines the [`Metric`] trait and the [`Stm`] and [`Mtm`] metrics.
+//! Defines the [`Metric`] and [`SequenceMetric`] traits, and the [`Stm`], [`Mtm`] and [`GapTurn`]
+//! metrics.
 
+use itertools::Itertools as _;
 use num_traits::{AsPrimitive, PrimInt};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -7,6 +9,9 @@ use serde::{Deserialize, Serialize};
 use crate::algorithm::r#move::r#move::Move;
 
 /// Defines a length function on [`Move`]s.
+///
+/// The length of a sequence of moves is the sum of the lengths of the moves (see
+/// [`SequenceMetric`]).
 pub trait Metric {
     /// Whether the metric preserves move-count parity. `true` for [`Stm`], `false` for [`Mtm`].
     const HAS_MOVECOUNT_PARITY: bool;
@@ -16,6 +21,48 @@ pub trait Metric {
     where
         T: PrimInt + 'static,
         u64: AsPrimitive<T>;
+
+    /// The amount of a move in the same direction as `mv` with amount at most `mv.amount()` that
+    /// has length `len`, or `None` if there is no such move. Used to split a move when slicing an
+    /// algorithm.
+    ///
+    /// The default implementation tries every amount in turn, so metrics where the amount can be
+    /// computed directly should override it.
+    #[must_use]
+    fn split_amount(mv: Move, len: u64) -> Option<u64> {
+        (1..=mv.amount()).find(|&amount| Self::len::<u64>(Move::new(mv.direction(), amount)) == len)
+    }
+}
+
+/// Defines a length function on sequences of [`Move`]s.
+///
+/// Every [`Metric`] implements this trait, where the length of a sequence is the sum of the
+/// lengths of its moves. Metrics where the length of a move depends on the moves around it, like
+/// [`GapTurn`], only implement this trait, so they can not be used where the length of a single
+/// move is needed (e.g. [`Algorithm::try_slice_by`]).
+///
+/// [`Algorithm::try_slice_by`]: ../algorithm/struct.Algorithm.html#method.try_slice_by
+pub trait SequenceMetric {
+    /// The length of a sequence of [`Move`]s.
+    fn len_moves<T, I>(moves: I) -> T
+    where
+        T: PrimInt + 'static,
+        u64: AsPrimitive<T>,
+        I: IntoIterator<Item = Move>;
+}
+
+impl<M: Metric> SequenceMetric for M {
+    fn len_moves<T, I>(moves: I) -> T
+    where
+        T: PrimInt + 'static,
+        u64: AsPrimitive<T>,
+        I: IntoIterator<Item = Move>,
+    {
+        moves
+            .into_iter()
+            .map(Self::len::<T>)
+            .fold(T::zero(), |a, b| a + b)
+    }
 }
 
 /// Single tile move metric, where moves like U5 have length 5, etc.
@@ -24,10 +71,22 @@ pub trait Metric {
 pub struct Stm;
 
 /// Multi tile move metric, where all moves have length 1.
+///
+/// This is also known as the row-move or block metric: sliding any number of tiles in a single
+/// row or column counts as one move. Consecutive moves along the same axis, like `R2 L`, each
+/// count separately, but they are merged by [`Algorithm::simplified`].
+///
+/// [`Algorithm::simplified`]: ../algorithm/struct.Algorithm.html#method.simplified
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Mtm;
 
+/// Gap turn metric, which counts the number of times the gap changes direction, e.g. `R2 D L U2`
+/// has length 3.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GapTurn;
+
 impl Metric for Stm {
     const HAS_MOVECOUNT_PARITY: bool = true;
 
@@ -38,6 +97,10 @@ impl Metric for Stm {
     {
         mv.amount().as_()
     }
+
+    fn split_amount(mv: Move, len: u64) -> Option<u64> {
+        (1..=mv.amount()).contains(&len).then_some(len)
+    }
 }
 
 impl Metric for Mtm {
@@ -50,4 +113,51 @@ impl Metric for Mtm {
     {
         T::one()
     }
+
+    fn split_amount(mv: Move, len: u64) -> Option<u64> {
+        (len == 1 && mv.amount() != 0).then_some(mv.amount())
+    }
+}
+
+impl SequenceMetric for GapTurn {
+    fn len_moves<T, I>(moves: I) -> T
+    where
+        T: PrimInt + 'static,
+        u64: AsPrimitive<T>,
+        I: IntoIterator<Item = Move>,
+    {
+        let segments = moves
+            .into_iter()
+            .filter(|m| m.amount() != 0)
+            .dedup_by(|a, b| a.direction() == b.direction())
+            .count() as u64;
+        segments.saturating_sub(1).as_()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::algorithm::algorithm::Algorithm;
+
+    #[test]
+    fn test_gap_turn() {
+        let a = Algorithm::from_str("R2 D L U2").unwrap();
+        assert_eq!(a.len::<GapTurn, u64>(), 3);
+
+        let a = Algorithm::from_str("R R D").unwrap();
+        assert_eq!(a.len::<GapTurn, u64>(), 1);
+
+        assert_eq!(Algorithm::from_str("U5").unwrap().len::<GapTurn, u64>(), 0);
+        assert_eq!(Algorithm::new().len::<GapTurn, u64>(), 0);
+    }
+
+    #[test]
+    fn test_slice_len() {
+        let a = Algorithm::from_str("R2 D L U2").unwrap();
+        let s = a.try_slice(1..5).unwrap();
+        assert_eq!(s.len::<GapTurn, u64>(), 3);
+    }
 }
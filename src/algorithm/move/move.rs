@@ -6,11 +6,14 @@ use std::{cmp::Ordering, fmt::Display, num::ParseIntError, ops::Add, str::FromSt
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::algorithm::{
-    as_slice::AsAlgorithmSlice,
-    direction::{Direction, ParseDirectionError},
-    display::r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort, MoveDisplay as _},
-    slice::AlgorithmSlice,
+use crate::{
+    algorithm::{
+        as_slice::AsAlgorithmSlice,
+        direction::{Direction, ParseDirectionError},
+        display::r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort, MoveDisplay as _},
+        slice::AlgorithmSlice,
+    },
+    puzzle::symmetry::Symmetry,
 };
 
 /// A (possibly multi-tile) move of a puzzle. Contains a direction and an amount.
@@ -89,6 +92,16 @@ impl Move {
         }
     }
 
+    /// Returns the move obtained by applying `symmetry` to the puzzle. This is given by applying
+    /// the symmetry to the direction and leaving the amount unchanged.
+    #[must_use]
+    pub fn apply_symmetry(&self, symmetry: Symmetry) -> Self {
+        Self {
+            direction: self.direction.apply_symmetry(symmetry),
+            amount: self.amount,
+        }
+    }
+
     /// Helper function for creating a [`DisplayLongSpaced`] around `self`.
     #[must_use]
     pub fn display_long_spaced(&self) -> DisplayLongSpaced {
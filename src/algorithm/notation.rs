@@ -0,0 +1,335 @@
+//! Defines the [`Notation`] type, which describes how [`Direction`]s are written as characters.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::algorithm::{
+    algorithm::{Algorithm, ParseAlgorithmError},
+    direction::Direction,
+    r#move::r#move::Move,
+};
+
+/// Error type for [`Notation`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NotationError {
+    /// Returned when the same character is used for more than one direction.
+    #[error("DuplicateCharacter: character {0} is used for more than one direction")]
+    DuplicateCharacter(char),
+
+    /// Returned when a character can not be used for a direction, because it is a digit or
+    /// whitespace.
+    #[error("InvalidCharacter: character {0} can not be a digit or whitespace")]
+    InvalidCharacter(char),
+}
+
+/// Describes how [`Direction`]s are written as characters, for parsing and displaying
+/// [`Algorithm`]s.
+///
+/// The characters are given in terms of the direction that a piece moves in. If `gap_relative` is
+/// set, directions are instead written as the direction that the gap moves in, which is the
+/// opposite direction, e.g. [`Direction::Up`] is written using the character for
+/// [`Direction::Down`].
+///
+/// The default notation is [`Notation::standard`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "NotationUnvalidated")
+)]
+pub struct Notation {
+    up: char,
+    left: char,
+    down: char,
+    right: char,
+    gap_relative: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+struct NotationUnvalidated {
+    up: char,
+    left: char,
+    down: char,
+    right: char,
+    gap_relative: bool,
+}
+
+impl TryFrom<NotationUnvalidated> for Notation {
+    type Error = NotationError;
+
+    fn try_from(value: NotationUnvalidated) -> Result<Self, Self::Error> {
+        let NotationUnvalidated {
+            up,
+            left,
+            down,
+            right,
+            gap_relative,
+        } = value;
+
+        Ok(Self::new(up, left, down, right)?.gap_relative(gap_relative))
+    }
+}
+
+impl Default for Notation {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl Notation {
+    /// Creates a new [`Notation`] using the given characters for the directions `Up`, `Left`,
+    /// `Down`, and `Right`.
+    pub fn new(up: char, left: char, down: char, right: char) -> Result<Self, NotationError> {
+        let chars = [up, left, down, right];
+
+        for (i, &c) in chars.iter().enumerate() {
+            if c.is_ascii_digit() || c.is_whitespace() {
+                return Err(NotationError::InvalidCharacter(c));
+            }
+
+            if chars[..i].contains(&c) {
+                return Err(NotationError::DuplicateCharacter(c));
+            }
+        }
+
+        Ok(Self {
+            up,
+            left,
+            down,
+            right,
+            gap_relative: false,
+        })
+    }
+
+    /// The standard notation, using the characters `U`, `L`, `D`, `R`.
+    #[must_use]
+    pub fn standard() -> Self {
+        Self {
+            up: 'U',
+            left: 'L',
+            down: 'D',
+            right: 'R',
+            gap_relative: false,
+        }
+    }
+
+    /// Lower case notation, using the characters `u`, `l`, `d`, `r`.
+    #[must_use]
+    pub fn lowercase() -> Self {
+        Self {
+            up: 'u',
+            left: 'l',
+            down: 'd',
+            right: 'r',
+            gap_relative: false,
+        }
+    }
+
+    /// Arrow notation, using the characters `↑`, `←`, `↓`, `→`.
+    #[must_use]
+    pub fn arrows() -> Self {
+        Self {
+            up: '↑',
+            left: '←',
+            down: '↓',
+            right: '→',
+            gap_relative: false,
+        }
+    }
+
+    /// Sets whether directions are written as the direction that the gap moves in, instead of the
+    /// direction that a piece moves in.
+    #[must_use]
+    pub fn gap_relative(mut self, gap_relative: bool) -> Self {
+        self.gap_relative = gap_relative;
+        self
+    }
+
+    /// Checks if directions are written as the direction that the gap moves in.
+    #[must_use]
+    pub fn is_gap_relative(&self) -> bool {
+        self.gap_relative
+    }
+
+    /// The character used to write `dir`.
+    #[must_use]
+    pub fn as_char(&self, dir: Direction) -> char {
+        let dir = if self.gap_relative {
+            dir.inverse()
+        } else {
+            dir
+        };
+
+        match dir {
+            Direction::Up => self.up,
+            Direction::Left => self.left,
+            Direction::Down => self.down,
+            Direction::Right => self.right,
+        }
+    }
+
+    /// The [`Direction`] written as `c`, or `None` if `c` is not used in this notation.
+    #[must_use]
+    pub fn direction(&self, c: char) -> Option<Direction> {
+        let dir = [
+            (self.up, Direction::Up),
+            (self.left, Direction::Left),
+            (self.down, Direction::Down),
+            (self.right, Direction::Right),
+        ]
+        .into_iter()
+        .find_map(|(ch, dir)| (ch == c).then_some(dir))?;
+
+        Some(if self.gap_relative {
+            dir.inverse()
+        } else {
+            dir
+        })
+    }
+
+    /// Parses an [`Algorithm`] written in this notation.
+    ///
+    /// See [`Algorithm::from_str`] for the format, which is the same except for the characters
+    /// used for the directions.
+    ///
+    /// [`Algorithm::from_str`]: ../algorithm/struct.Algorithm.html#method.from_str
+    pub fn parse_algorithm(&self, s: &str) -> Result<Algorithm, ParseAlgorithmError> {
+        let mut alg = Algorithm::default();
+
+        let mut dir = None;
+        let mut amount = None;
+
+        for c in s.chars() {
+            // Direction character is the start of a new move
+            if let Some(d) = self.direction(c) {
+                // Push the previous move, if there was one
+                if let Some(dir) = dir {
+                    alg.push(Move::new(dir, amount.unwrap_or(1)));
+                }
+
+                // Set the new direction and default amount for the next move
+                dir = Some(d);
+                amount = None;
+            }
+            // The number after a move
+            else if let Some(d) = c.to_digit(10) {
+                let d = d as u64;
+
+                // Must have a direction before an amount
+                if dir.is_none() {
+                    return Err(ParseAlgorithmError::MissingDirection);
+                }
+
+                // Append the next digit to the number
+                if let Some(a) = amount {
+                    amount = Some(
+                        a.checked_mul(10)
+                            .ok_or(ParseAlgorithmError::Overflow)?
+                            .checked_add(d)
+                            .ok_or(ParseAlgorithmError::Overflow)?,
+                    );
+                } else {
+                    amount = Some(d);
+                }
+            }
+            // A whitespace character signals the end of a move
+            else if c.is_whitespace() {
+                // Push the previous move, if there was one
+                if let Some(dir) = dir {
+                    alg.push(Move::new(dir, amount.unwrap_or(1)));
+                }
+
+                // Direction and amount for the next move are unknown
+                dir = None;
+                amount = None;
+            }
+            // Any other character is invalid
+            else {
+                return Err(ParseAlgorithmError::InvalidCharacter(c));
+            }
+        }
+
+        // Push the last move
+        if let Some(dir) = dir {
+            alg.push(Move::new(dir, amount.unwrap_or(1)));
+        }
+
+        Ok(alg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Notation::new('N', 'W', 'S', 'E').is_ok());
+        assert_eq!(
+            Notation::new('N', 'W', 'N', 'E'),
+            Err(NotationError::DuplicateCharacter('N'))
+        );
+        assert_eq!(
+            Notation::new('N', '1', 'S', 'E'),
+            Err(NotationError::InvalidCharacter('1'))
+        );
+        assert_eq!(
+            Notation::new('N', 'W', ' ', 'E'),
+            Err(NotationError::InvalidCharacter(' '))
+        );
+    }
+
+    #[test]
+    fn test_as_char() {
+        let n = Notation::arrows();
+        assert_eq!(n.as_char(Direction::Up), '↑');
+        assert_eq!(n.as_char(Direction::Right), '→');
+
+        let n = n.gap_relative(true);
+        assert_eq!(n.as_char(Direction::Up), '↓');
+        assert_eq!(n.as_char(Direction::Right), '←');
+    }
+
+    #[test]
+    fn test_direction() {
+        let n = Notation::lowercase();
+        assert_eq!(n.direction('u'), Some(Direction::Up));
+        assert_eq!(n.direction('U'), None);
+
+        let n = n.gap_relative(true);
+        assert_eq!(n.direction('u'), Some(Direction::Down));
+        assert_eq!(n.direction('l'), Some(Direction::Right));
+    }
+
+    #[test]
+    fn test_parse_algorithm() {
+        let alg = Algorithm::from_str("U2RDL3").unwrap();
+
+        assert_eq!(
+            Notation::standard().parse_algorithm("U2RDL3"),
+            Ok(alg.clone())
+        );
+        assert_eq!(
+            Notation::lowercase().parse_algorithm("u2r d l3"),
+            Ok(alg.clone())
+        );
+        assert_eq!(
+            Notation::arrows().parse_algorithm("↑2→↓←3"),
+            Ok(alg.clone())
+        );
+        assert_eq!(
+            Notation::standard()
+                .gap_relative(true)
+                .parse_algorithm("D2LUR3"),
+            Ok(alg)
+        );
+        assert_eq!(
+            Notation::lowercase().parse_algorithm("u2R"),
+            Err(ParseAlgorithmError::InvalidCharacter('R'))
+        );
+    }
+}
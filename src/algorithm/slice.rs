@@ -15,7 +15,7 @@ use crate::{
             algorithm::{AlgorithmDisplay as _, DisplaySpaced, DisplayUnspaced},
             r#move::{DisplayLongSpaced, DisplayLongUnspaced, DisplayShort},
         },
-        metric::{Metric, Mtm, Stm},
+        metric::{Mtm, SequenceMetric, Stm},
         moves::Moves,
         r#move::r#move::{Move, MoveSum},
     },
@@ -33,18 +33,20 @@ pub struct AlgorithmSlice<'a> {
 }
 
 impl AlgorithmSlice<'_> {
-    /// The length of the slice in the [`Metric`] `M`.
+    /// The length of the slice in the [`SequenceMetric`] `M`.
     #[must_use]
     pub fn len<M, T>(&self) -> T
     where
-        M: Metric,
+        M: SequenceMetric,
         T: PrimInt + Sum + 'static,
         u64: AsPrimitive<T>,
     {
-        self.moves().map(|m| M::len::<T>(m)).sum()
+        M::len_moves(self.moves())
     }
 
     /// The length of the slice in the [`Stm`] [`Metric`].
+    ///
+    /// [`Metric`]: crate::algorithm::metric::Metric
     #[must_use]
     pub fn len_stm<T>(&self) -> T
     where
@@ -55,6 +57,8 @@ impl AlgorithmSlice<'_> {
     }
 
     /// The length of the slice in the [`Mtm`] [`Metric`].
+    ///
+    /// [`Metric`]: crate::algorithm::metric::Metric
     #[must_use]
     pub fn len_mtm<T>(&self) -> T
     where
@@ -145,4 +145,5 @@
 
 pub mod algorithm;
 pub mod puzzle;
+pub mod reconstruction;
 pub mod solver;
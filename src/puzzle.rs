@@ -3,11 +3,14 @@
 //!
 //! [`SlidingPuzzle`]: sliding_puzzle/trait.SlidingPuzzle.html
 
+pub mod apply;
 pub mod color_scheme;
 pub mod coloring;
 pub mod display;
 pub mod grids;
+pub mod history;
 pub mod label;
+pub mod obstacle;
 pub mod puzzle;
 pub mod render;
 pub mod scrambler;
@@ -16,3 +19,4 @@ pub mod sliding_puzzle;
 pub mod small;
 pub mod solvable;
 pub mod solved_state;
+pub mod symmetry;
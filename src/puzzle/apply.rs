@@ -0,0 +1,167 @@
+//! Defines the error type returned by [`SlidingPuzzle::apply_alg_checked`], and the [`States`]
+//! iterator returned by [`SlidingPuzzle::states`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    algorithm::{moves::Moves, r#move::r#move::Move},
+    puzzle::sliding_puzzle::SlidingPuzzle,
+};
+
+/// Error type for [`SlidingPuzzle::apply_alg_checked`] and [`States`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ApplyAlgError<P> {
+    /// Returned when a move of the algorithm can not be applied.
+    #[error(
+        "IllegalMove: move {mv} at index {move_index} (single tile move {stm_index}) can not be \
+        applied"
+    )]
+    IllegalMove {
+        /// The index of the move in the algorithm, counting multi-tile moves.
+        move_index: u64,
+
+        /// The number of single tile moves in the algorithm before the move.
+        stm_index: u64,
+
+        /// The move that could not be applied.
+        mv: Move,
+
+        /// The state of the puzzle just before the move.
+        state: P,
+    },
+}
+
+/// Iterator over the states of a puzzle while an algorithm is applied to it. Yields the state
+/// after each move, or an error if a move can not be applied, after which the iterator ends.
+///
+/// Created by [`SlidingPuzzle::states`].
+#[derive(Clone, Debug)]
+pub struct States<'a, P> {
+    puzzle: P,
+    moves: Moves<'a>,
+    move_index: u64,
+    stm_index: u64,
+    finished: bool,
+}
+
+impl<'a, P> States<'a, P> {
+    pub(super) fn new(puzzle: P, moves: Moves<'a>) -> Self {
+        Self {
+            puzzle,
+            moves,
+            move_index: 0,
+            stm_index: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<P> Iterator for States<'_, P>
+where
+    P: SlidingPuzzle + Clone,
+{
+    type Item = Result<P, ApplyAlgError<P>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let Some(mv) = self.moves.next() else {
+            self.finished = true;
+            return None;
+        };
+
+        if !self.puzzle.can_apply_move(mv) {
+            self.finished = true;
+            return Some(Err(ApplyAlgError::IllegalMove {
+                move_index: self.move_index,
+                stm_index: self.stm_index,
+                mv,
+                state: self.puzzle.clone(),
+            }));
+        }
+
+        self.puzzle.apply_move(mv);
+        self.move_index += 1;
+        self.stm_index += mv.amount;
+
+        Some(Ok(self.puzzle.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::{
+        algorithm::{algorithm::Algorithm, direction::Direction},
+        puzzle::puzzle::Puzzle,
+    };
+
+    #[test]
+    fn test_apply_alg_checked() {
+        let mut p = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let alg = Algorithm::from_str("DR2U").unwrap();
+        assert_eq!(p.apply_alg_checked(&alg), Ok(()));
+        assert_eq!(p, Puzzle::from_str("1 2 3/7 4 5/0 8 6").unwrap());
+    }
+
+    #[test]
+    fn test_apply_alg_checked_error() {
+        let mut p = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let original = p.clone();
+        let alg = Algorithm::from_str("DR2L2R3").unwrap();
+        assert_eq!(
+            p.apply_alg_checked(&alg),
+            Err(ApplyAlgError::IllegalMove {
+                move_index: 3,
+                stm_index: 5,
+                mv: Move::new(Direction::Right, 3),
+                state: Puzzle::from_str("1 2 3/4 5 0/7 8 6").unwrap(),
+            })
+        );
+        assert_eq!(p, original);
+    }
+
+    #[test]
+    fn test_states() {
+        let p = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let alg = Algorithm::from_str("DR2U").unwrap();
+        let states = p.states(&alg).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            states,
+            vec![
+                Puzzle::from_str("1 2 3/4 5 0/7 8 6").unwrap(),
+                Puzzle::from_str("1 2 3/0 4 5/7 8 6").unwrap(),
+                Puzzle::from_str("1 2 3/7 4 5/0 8 6").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_states_error() {
+        let p = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let alg = Algorithm::from_str("DUU").unwrap();
+        let mut states = p.states(&alg);
+        assert_eq!(
+            states.next(),
+            Some(Ok(Puzzle::from_str("1 2 3/4 5 0/7 8 6").unwrap()))
+        );
+        assert_eq!(states.next(), Some(Ok(p.clone())));
+        assert_eq!(
+            states.next(),
+            Some(Err(ApplyAlgError::IllegalMove {
+                move_index: 2,
+                stm_index: 2,
+                mv: Move::new(Direction::Up, 1),
+                state: p,
+            }))
+        );
+        assert_eq!(states.next(), None);
+    }
+}
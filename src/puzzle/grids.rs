@@ -11,3 +11,21 @@ pub trait Grids {
     /// the given [`Size`].
     fn grid_containing_pos(&self, size: Size, pos: (u64, u64)) -> Rect;
 }
+
+impl<G: Grids + ?Sized> Grids for &G {
+    fn grid_containing_pos(&self, size: Size, pos: (u64, u64)) -> Rect {
+        (**self).grid_containing_pos(size, pos)
+    }
+}
+
+impl<G: Grids + ?Sized> Grids for &mut G {
+    fn grid_containing_pos(&self, size: Size, pos: (u64, u64)) -> Rect {
+        (**self).grid_containing_pos(size, pos)
+    }
+}
+
+impl<G: Grids + ?Sized> Grids for Box<G> {
+    fn grid_containing_pos(&self, size: Size, pos: (u64, u64)) -> Rect {
+        (**self).grid_containing_pos(size, pos)
+    }
+}
@@ -0,0 +1,365 @@
+//! Defines the [`History`] type, a wrapper around a [`SlidingPuzzle`] that records the moves
+//! applied to it.
+
+use crate::{
+    algorithm::{
+        algorithm::Algorithm, as_slice::AsAlgorithmSlice, direction::Direction,
+        r#move::r#move::Move,
+    },
+    puzzle::{size::Size, sliding_puzzle::SlidingPuzzle},
+};
+
+/// A wrapper around a [`SlidingPuzzle`] that records every [`Move`] applied to it, and supports
+/// undoing and redoing moves.
+///
+/// Applying a new move after undoing some moves discards the undone moves, so they can no longer
+/// be redone. Any change to the puzzle that is not a move (e.g. [`SlidingPuzzle::swap_pieces`] or
+/// [`SlidingPuzzle::reset`]) clears the history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct History<P> {
+    puzzle: P,
+    moves: Vec<Move>,
+    position: usize,
+}
+
+impl<P: SlidingPuzzle> History<P> {
+    /// Creates a new [`History`] with no recorded moves.
+    #[must_use]
+    pub fn new(puzzle: P) -> Self {
+        Self {
+            puzzle,
+            moves: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns a reference to the inner puzzle.
+    #[must_use]
+    pub fn inner(&self) -> &P {
+        &self.puzzle
+    }
+
+    /// Extracts the inner puzzle, consuming `self`.
+    #[must_use]
+    pub fn into_inner(self) -> P {
+        self.puzzle
+    }
+
+    /// All recorded moves, including those that have been undone and can be redone.
+    #[must_use]
+    pub fn recorded_moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// The moves that are currently applied to the puzzle.
+    #[must_use]
+    pub fn applied_moves(&self) -> &[Move] {
+        &self.moves[..self.position]
+    }
+
+    /// The number of moves that are currently applied to the puzzle.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Checks if there is a move that can be undone.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    /// Checks if there is a move that can be redone.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        self.position < self.moves.len()
+    }
+
+    /// Undoes the last applied move.
+    ///
+    /// Returns the move that was undone, or `None` if there are no moves to undo.
+    pub fn undo(&mut self) -> Option<Move> {
+        self.can_undo().then(|| {
+            self.position -= 1;
+            let mv = self.moves[self.position];
+            self.puzzle.apply_move(mv.inverse());
+            mv
+        })
+    }
+
+    /// Redoes the last undone move.
+    ///
+    /// Returns the move that was redone, or `None` if there are no moves to redo.
+    pub fn redo(&mut self) -> Option<Move> {
+        self.can_redo().then(|| {
+            let mv = self.moves[self.position];
+            self.position += 1;
+            self.puzzle.apply_move(mv);
+            mv
+        })
+    }
+
+    /// Undoes or redoes moves until exactly `index` of the recorded moves are applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of recorded moves.
+    pub fn seek(&mut self, index: usize) {
+        assert!(
+            self.try_seek(index),
+            "index {index} is out of range for a history of length {}",
+            self.moves.len(),
+        );
+    }
+
+    /// See [`History::seek`].
+    ///
+    /// Returns `true` if the seek was successful, `false` otherwise.
+    pub fn try_seek(&mut self, index: usize) -> bool {
+        if index > self.moves.len() {
+            return false;
+        }
+
+        while self.position > index {
+            self.undo();
+        }
+
+        while self.position < index {
+            self.redo();
+        }
+
+        true
+    }
+
+    /// Clears the recorded moves, without changing the state of the puzzle.
+    pub fn clear_history(&mut self) {
+        self.moves.clear();
+        self.position = 0;
+    }
+
+    /// Returns the applied moves as an [`Algorithm`], with consecutive moves in the same direction
+    /// combined using [`Algorithm::push_combine`].
+    #[must_use]
+    pub fn to_algorithm(&self) -> Algorithm {
+        let mut alg = Algorithm::new();
+        for &mv in self.applied_moves() {
+            alg.push_combine(mv);
+        }
+        alg
+    }
+
+    /// Returns the applied moves as an [`Algorithm`], with consecutive moves along the same axis
+    /// combined or cancelled using [`Algorithm::push_simplify`].
+    #[must_use]
+    pub fn to_simplified_algorithm(&self) -> Algorithm {
+        let mut alg = Algorithm::new();
+        for &mv in self.applied_moves() {
+            alg.push_simplify(mv);
+        }
+        alg
+    }
+
+    fn record(&mut self, mv: Move) {
+        if mv.amount != 0 {
+            self.moves.truncate(self.position);
+            self.moves.push(mv);
+            self.position += 1;
+        }
+    }
+}
+
+impl<P: SlidingPuzzle> From<P> for History<P> {
+    fn from(puzzle: P) -> Self {
+        Self::new(puzzle)
+    }
+}
+
+impl<P: SlidingPuzzle> SlidingPuzzle for History<P> {
+    type Piece = P::Piece;
+
+    fn size(&self) -> Size {
+        self.puzzle.size()
+    }
+
+    fn try_piece_position(&self, piece: Self::Piece) -> Option<u64> {
+        self.puzzle.try_piece_position(piece)
+    }
+
+    fn gap_position(&self) -> u64 {
+        self.puzzle.gap_position()
+    }
+
+    fn gap_position_xy(&self) -> (u64, u64) {
+        self.puzzle.gap_position_xy()
+    }
+
+    fn reset(&mut self) {
+        self.clear_history();
+        self.puzzle.reset();
+    }
+
+    fn is_solved(&self) -> bool {
+        self.puzzle.is_solved()
+    }
+
+    fn is_solvable(&self) -> bool {
+        self.puzzle.is_solvable()
+    }
+
+    fn solved_pos(&self, piece: Self::Piece) -> u64 {
+        self.puzzle.solved_pos(piece)
+    }
+
+    fn piece_at(&self, idx: u64) -> Self::Piece {
+        self.puzzle.piece_at(idx)
+    }
+
+    unsafe fn piece_at_unchecked(&self, idx: u64) -> Self::Piece {
+        self.puzzle.piece_at_unchecked(idx)
+    }
+
+    fn swap_pieces(&mut self, idx1: u64, idx2: u64) {
+        self.clear_history();
+        self.puzzle.swap_pieces(idx1, idx2);
+    }
+
+    unsafe fn swap_pieces_unchecked(&mut self, idx1: u64, idx2: u64) {
+        self.clear_history();
+        self.puzzle.swap_pieces_unchecked(idx1, idx2);
+    }
+
+    fn can_move_dir(&self, dir: Direction) -> bool {
+        self.puzzle.can_move_dir(dir)
+    }
+
+    fn move_dir(&mut self, dir: Direction) {
+        self.puzzle.move_dir(dir);
+        self.record(Move::from(dir));
+    }
+
+    unsafe fn move_dir_unchecked(&mut self, dir: Direction) {
+        self.puzzle.move_dir_unchecked(dir);
+        self.record(Move::from(dir));
+    }
+
+    fn can_apply_move(&self, mv: Move) -> bool {
+        self.puzzle.can_apply_move(mv)
+    }
+
+    fn apply_move(&mut self, mv: Move) {
+        self.puzzle.apply_move(mv);
+        self.record(mv);
+    }
+
+    unsafe fn apply_move_unchecked(&mut self, mv: Move) {
+        self.puzzle.apply_move_unchecked(mv);
+        self.record(mv);
+    }
+
+    fn can_move_position_xy(&self, pos: (u64, u64)) -> bool {
+        self.puzzle.can_move_position_xy(pos)
+    }
+
+    fn can_apply_alg<'a, Alg>(&self, alg: &'a Alg) -> bool
+    where
+        Alg: AsAlgorithmSlice<'a>,
+    {
+        self.puzzle.can_apply_alg(alg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::puzzle::puzzle::Puzzle;
+
+    fn history() -> History<Puzzle> {
+        let mut h = History::new(Puzzle::new(Size::new(4, 4).unwrap()));
+        h.apply_alg(&Algorithm::from_str("DRRULDDU").unwrap());
+        h
+    }
+
+    #[test]
+    fn test_record() {
+        let h = history();
+        assert_eq!(h.position(), 8);
+        assert_eq!(h.recorded_moves().len(), 8);
+        assert_eq!(h.to_algorithm(), Algorithm::from_str("DR2ULD2U").unwrap());
+        assert_eq!(
+            h.to_simplified_algorithm(),
+            Algorithm::from_str("DR2ULD").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut h = history();
+        let state = h.inner().clone();
+
+        assert_eq!(h.undo(), Some(Move::new(Direction::Up, 1)));
+        assert_eq!(h.undo(), Some(Move::new(Direction::Down, 1)));
+        assert_eq!(h.position(), 6);
+        assert!(h.can_redo());
+
+        assert_eq!(h.redo(), Some(Move::new(Direction::Down, 1)));
+        assert_eq!(h.redo(), Some(Move::new(Direction::Up, 1)));
+        assert_eq!(h.redo(), None);
+        assert_eq!(h.inner(), &state);
+
+        while h.undo().is_some() {}
+        assert!(h.is_solved());
+        assert!(!h.can_undo());
+    }
+
+    #[test]
+    fn test_new_move_discards_redo() {
+        let mut h = history();
+        h.undo();
+        h.undo();
+        h.move_dir(Direction::Left);
+        assert_eq!(h.recorded_moves().len(), 7);
+        assert!(!h.can_redo());
+        assert_eq!(h.to_algorithm(), Algorithm::from_str("DR2ULDL").unwrap());
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut h = history();
+        let mut p = Puzzle::new(Size::new(4, 4).unwrap());
+        p.apply_alg(&Algorithm::from_str("DRRU").unwrap());
+
+        h.seek(4);
+        assert_eq!(h.inner(), &p);
+        assert_eq!(h.recorded_moves().len(), 8);
+        assert!(!h.try_seek(9));
+
+        h.seek(0);
+        assert!(h.is_solved());
+        h.seek(8);
+        assert_eq!(h.to_algorithm(), Algorithm::from_str("DR2ULD2U").unwrap());
+    }
+
+    #[test]
+    fn test_move_position() {
+        let mut h = History::new(Puzzle::new(Size::new(4, 4).unwrap()));
+        assert!(h.try_move_position_xy((0, 3)));
+        assert!(h.try_move_position_xy((0, 0)));
+        assert!(!h.try_move_position_xy((2, 2)));
+        assert_eq!(h.to_algorithm(), Algorithm::from_str("R3D3").unwrap());
+    }
+
+    #[test]
+    fn test_swap_clears_history() {
+        let mut h = history();
+        h.swap_non_gap_pieces(0, 1);
+        assert!(h.recorded_moves().is_empty());
+        assert!(!h.can_undo());
+
+        let mut h = history();
+        h.reset();
+        assert!(h.recorded_moves().is_empty());
+        assert!(h.is_solved());
+    }
+}
@@ -0,0 +1,637 @@
+//! Defines the [`ObstaclePuzzle`] type, a [`SlidingPuzzle`] containing fixed cells that can not be
+//! entered by the gap or any piece.
+
+use std::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    algorithm::{as_slice::AsAlgorithmSlice, direction::Direction, r#move::r#move::Move},
+    puzzle::{size::Size, sliding_puzzle::SlidingPuzzle},
+};
+
+/// Error type for [`ObstaclePuzzle`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ObstaclePuzzleError {
+    /// Returned when an obstacle position is outside the bounds of the puzzle.
+    #[error("PositionOutOfBounds: position {pos:?} is out of bounds on a {size} puzzle")]
+    PositionOutOfBounds {
+        /// Size of the puzzle.
+        size: Size,
+        /// Obstacle position.
+        pos: (u64, u64),
+    },
+
+    /// Returned when the piece in an obstacle cell is not the piece that belongs there in the
+    /// solved state.
+    #[error("PieceNotFixed: the piece in obstacle position {0:?} is not in its solved position")]
+    PieceNotFixed((u64, u64)),
+
+    /// Returned when an obstacle is placed on the solved position of the gap (the bottom right
+    /// corner), in which case the puzzle could never be solved.
+    #[error("GapSolvedPosition: the solved position of the gap can not be an obstacle")]
+    GapSolvedPosition,
+}
+
+/// A [`SlidingPuzzle`] with a set of obstacle cells. The gap can never move into an obstacle cell,
+/// so the pieces in those cells never move.
+///
+/// The pieces in the obstacle cells must be the pieces that belong there in the solved state. Note
+/// that functions that swap pieces directly (e.g. [`SlidingPuzzle::swap_pieces`], or scramblers
+/// that use it) do not respect the obstacles.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ObstaclePuzzle<P> {
+    puzzle: P,
+    obstacles: Vec<bool>,
+}
+
+impl<P: SlidingPuzzle> ObstaclePuzzle<P> {
+    /// Creates a new [`ObstaclePuzzle`] from a puzzle and a list of obstacle positions.
+    pub fn new(puzzle: P, obstacles: &[(u64, u64)]) -> Result<Self, ObstaclePuzzleError> {
+        let size = puzzle.size();
+        let (w, h) = size.into();
+        let mut mask = vec![false; size.area() as usize];
+
+        for &pos in obstacles {
+            if !size.is_within_bounds(pos) {
+                return Err(ObstaclePuzzleError::PositionOutOfBounds { size, pos });
+            }
+
+            if pos == (w - 1, h - 1) {
+                return Err(ObstaclePuzzleError::GapSolvedPosition);
+            }
+
+            if puzzle.solved_pos_xy(puzzle.piece_at_xy(pos)) != pos {
+                return Err(ObstaclePuzzleError::PieceNotFixed(pos));
+            }
+
+            mask[(pos.0 + w * pos.1) as usize] = true;
+        }
+
+        Ok(Self {
+            puzzle,
+            obstacles: mask,
+        })
+    }
+
+    /// Returns a reference to the inner puzzle.
+    #[must_use]
+    pub fn inner(&self) -> &P {
+        &self.puzzle
+    }
+
+    /// Extracts the inner puzzle, consuming `self`.
+    #[must_use]
+    pub fn into_inner(self) -> P {
+        self.puzzle
+    }
+
+    /// Checks if position `idx` is an obstacle.
+    #[must_use]
+    pub fn is_obstacle(&self, idx: u64) -> bool {
+        self.obstacles.get(idx as usize).copied().unwrap_or(false)
+    }
+
+    /// Checks if position `(x, y)` is an obstacle.
+    #[must_use]
+    pub fn is_obstacle_xy(&self, (x, y): (u64, u64)) -> bool {
+        self.size().is_within_bounds((x, y)) && self.is_obstacle(x + self.size().width() * y)
+    }
+
+    /// The number of pieces in the puzzle that are not fixed in obstacle cells.
+    #[must_use]
+    pub fn num_movable_pieces(&self) -> u64 {
+        self.num_pieces() - self.obstacles.iter().filter(|&&b| b).count() as u64
+    }
+
+    /// An iterator over the positions of the obstacles, as (x, y) coordinates.
+    pub fn obstacles(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        let w = self.size().width();
+        self.obstacles
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b)
+            .map(move |(i, _)| (i as u64 % w, i as u64 / w))
+    }
+
+    /// Checks that every piece that can not be reached by the gap is already in its solved
+    /// position, and that the usual permutation parity condition is satisfied.
+    ///
+    /// This is a necessary condition for the puzzle to be solvable, but it is only sufficient when
+    /// the free cells reachable from the gap form a region that remains connected after removing
+    /// any single cell, and is not a simple loop. For example, the pieces in a loop of cells around
+    /// an obstacle can only be rotated. Use [`SlidingPuzzle::is_solvable`] for an exact check.
+    #[must_use]
+    pub fn passes_parity_check(&self) -> bool {
+        let size = self.size();
+        let (w, h) = size.into();
+        let area = size.area() as usize;
+        let region = self.region();
+
+        // Pieces that can't be reached must already be solved, and pieces that can be reached must
+        // belong in a reachable position
+        for i in 0..area {
+            let solved_pos = self.solved_pos(self.piece_at(i as u64)) as usize;
+            let reachable = region.index[i].is_some();
+            if reachable != region.index[solved_pos].is_some() || (!reachable && solved_pos != i) {
+                return false;
+            }
+        }
+
+        // Parity of the permutation must match the parity of the gap distance from its solved
+        // position
+        let mut seen = vec![false; area];
+        let mut parity = false;
+        for i in 0..area {
+            let mut index = i;
+            let mut cycle_len = 0;
+            while !seen[index] {
+                seen[index] = true;
+                cycle_len += 1;
+                index = self.solved_pos(self.piece_at(index as u64)) as usize;
+            }
+
+            if cycle_len > 0 && cycle_len % 2 == 0 {
+                parity = !parity;
+            }
+        }
+
+        let (gx, gy) = self.gap_position_xy();
+        let gap_distance = (w - 1 - gx) + (h - 1 - gy);
+
+        parity == (gap_distance % 2 == 1)
+    }
+
+    /// Finds the region of free cells that can be reached by the gap.
+    fn region(&self) -> Region {
+        let size = self.size();
+        let (w, h) = size.into();
+
+        let mut cells = vec![self.gap_position()];
+        let mut index = vec![None; size.area() as usize];
+        index[self.gap_position() as usize] = Some(0);
+
+        let mut neighbours = Vec::new();
+        let mut i = 0;
+        while let Some(&cell) = cells.get(i) {
+            let (x, y) = (cell % w, cell / w);
+            let adjacent = [
+                (x + 1 < w).then(|| cell + 1),
+                (y + 1 < h).then(|| cell + w),
+                (x > 0).then(|| cell - 1),
+                (y > 0).then(|| cell - w),
+            ];
+
+            let mut list = Vec::new();
+            for adj in adjacent.into_iter().flatten() {
+                if self.is_obstacle(adj) {
+                    continue;
+                }
+
+                let j = *index[adj as usize].get_or_insert_with(|| {
+                    cells.push(adj);
+                    cells.len() - 1
+                });
+                list.push(j);
+            }
+
+            neighbours.push(list);
+            i += 1;
+        }
+
+        Region {
+            cells,
+            index,
+            neighbours,
+        }
+    }
+
+    /// Returns the position of the gap after applying `mv` with the gap in position `(gx, gy)`, or
+    /// `None` if the move would take the gap out of bounds or through an obstacle.
+    fn gap_after_move(&self, (gx, gy): (u64, u64), mv: Move) -> Option<(u64, u64)> {
+        let (mut x, mut y) = (gx, gy);
+
+        for _ in 0..mv.amount {
+            (x, y) = match mv.direction {
+                Direction::Up => (x, y + 1),
+                Direction::Left => (x + 1, y),
+                Direction::Down => (x, y.checked_sub(1)?),
+                Direction::Right => (x.checked_sub(1)?, y),
+            };
+
+            if !self.size().is_within_bounds((x, y)) || self.is_obstacle_xy((x, y)) {
+                return None;
+            }
+        }
+
+        Some((x, y))
+    }
+}
+
+impl<P: SlidingPuzzle> SlidingPuzzle for ObstaclePuzzle<P> {
+    type Piece = P::Piece;
+
+    fn size(&self) -> Size {
+        self.puzzle.size()
+    }
+
+    fn try_piece_position(&self, piece: Self::Piece) -> Option<u64> {
+        self.puzzle.try_piece_position(piece)
+    }
+
+    fn gap_position(&self) -> u64 {
+        self.puzzle.gap_position()
+    }
+
+    fn gap_position_xy(&self) -> (u64, u64) {
+        self.puzzle.gap_position_xy()
+    }
+
+    fn reset(&mut self) {
+        self.puzzle.reset();
+    }
+
+    /// Checks if the puzzle is solvable.
+    ///
+    /// After moving the gap to its solved position, the pieces in each block of the region of cells
+    /// reachable by the gap (a maximal part of the region that remains connected after removing any
+    /// single cell) can only be permuted amongst themselves, excluding the cell of the block that is
+    /// closest to the solved position of the gap. The pieces in a block that is a simple loop can
+    /// only be rotated, and by Wilson's theorem, any even permutation of the pieces in any other
+    /// block can be reached.
+    fn is_solvable(&self) -> bool {
+        if !self.passes_parity_check() {
+            return false;
+        }
+
+        let region = self.region();
+        let len = region.cells.len();
+
+        let (Some(home), Some(mut gap)) = (
+            region.index[self.size().area() as usize - 1],
+            region.index[self.gap_position() as usize],
+        ) else {
+            return false;
+        };
+
+        // The solved position of the piece in each cell of the region
+        let Some(mut state) = region
+            .cells
+            .iter()
+            .map(|&i| region.index[self.solved_pos(self.piece_at(i)) as usize])
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        // Move the gap to its solved position
+        let parent = region.spanning_tree(home);
+        while let Some(p) = parent[gap] {
+            state.swap(gap, p);
+            gap = p;
+        }
+
+        let blocks = region.blocks(home);
+
+        let mut block_of = vec![usize::MAX; len];
+        for (b, block) in blocks.iter().enumerate() {
+            for &v in &block[1..] {
+                block_of[v] = b;
+            }
+        }
+
+        let mut seen = vec![false; len];
+        blocks.iter().enumerate().all(|(b, block)| {
+            let (entry, cells) = (block[0], &block[1..]);
+
+            if cells.iter().any(|&v| block_of[state[v]] != b) {
+                return false;
+            }
+
+            let in_block = |v: usize| v == entry || block_of[v] == b;
+            let edges = block
+                .iter()
+                .map(|&v| {
+                    region.neighbours[v]
+                        .iter()
+                        .filter(|&&n| in_block(n))
+                        .count()
+                })
+                .sum::<usize>()
+                / 2;
+
+            if block.len() > 2 && edges == block.len() {
+                // The block is a loop, so the pieces can only be rotated around it
+                let mut order = Vec::with_capacity(cells.len());
+                let (mut prev, mut cur) = (entry, cells[0]);
+                while cur != entry {
+                    order.push(cur);
+                    let next = region.neighbours[cur]
+                        .iter()
+                        .copied()
+                        .find(|&n| n != prev && in_block(n));
+                    (prev, cur) = (cur, next.unwrap_or(entry));
+                }
+
+                let offset = order.iter().position(|&v| v == state[order[0]]);
+                offset.is_some_and(|k| {
+                    (0..order.len()).all(|i| state[order[i]] == order[(i + k) % order.len()])
+                })
+            } else {
+                // The permutation of the pieces must be even
+                let mut parity = false;
+                for &v in cells {
+                    let mut index = v;
+                    let mut cycle_len = 0;
+                    while !seen[index] {
+                        seen[index] = true;
+                        cycle_len += 1;
+                        index = state[index];
+                    }
+
+                    if cycle_len > 0 && cycle_len % 2 == 0 {
+                        parity = !parity;
+                    }
+                }
+
+                !parity
+            }
+        })
+    }
+
+    fn solved_pos(&self, piece: Self::Piece) -> u64 {
+        self.puzzle.solved_pos(piece)
+    }
+
+    fn piece_at(&self, idx: u64) -> Self::Piece {
+        self.puzzle.piece_at(idx)
+    }
+
+    unsafe fn piece_at_unchecked(&self, idx: u64) -> Self::Piece {
+        self.puzzle.piece_at_unchecked(idx)
+    }
+
+    fn swap_pieces(&mut self, idx1: u64, idx2: u64) {
+        self.puzzle.swap_pieces(idx1, idx2);
+    }
+
+    fn swap_piece_with_gap(&mut self, idx: u64) {
+        self.puzzle.swap_piece_with_gap(idx);
+    }
+
+    fn can_move_dir(&self, dir: Direction) -> bool {
+        self.can_apply_move(Move::from(dir))
+    }
+
+    fn can_apply_move(&self, mv: Move) -> bool {
+        self.gap_after_move(self.gap_position_xy(), mv).is_some()
+    }
+
+    fn can_move_position_xy(&self, (x, y): (u64, u64)) -> bool {
+        let (gx, gy) = self.gap_position_xy();
+
+        let mv = if x == gx && y >= gy {
+            Move::new(Direction::Up, y - gy)
+        } else if x == gx {
+            Move::new(Direction::Down, gy - y)
+        } else if y == gy && x > gx {
+            Move::new(Direction::Left, x - gx)
+        } else if y == gy {
+            Move::new(Direction::Right, gx - x)
+        } else {
+            return false;
+        };
+
+        self.size().is_within_bounds((x, y)) && self.can_apply_move(mv)
+    }
+
+    fn can_apply_alg<'a, Alg>(&self, alg: &'a Alg) -> bool
+    where
+        Alg: AsAlgorithmSlice<'a>,
+    {
+        let mut gap = self.gap_position_xy();
+
+        for mv in alg.as_slice().moves() {
+            match self.gap_after_move(gap, mv) {
+                Some(g) => gap = g,
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// The region of free cells that can be reached by the gap, as a graph with edges between adjacent
+/// cells.
+struct Region {
+    /// The cells in the region.
+    cells: Vec<u64>,
+    /// The index in `cells` of each cell of the puzzle, or `None` if it is not in the region.
+    index: Vec<Option<usize>>,
+    /// The indices in `cells` of the neighbours of each cell.
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Region {
+    /// A breadth-first spanning tree of the region rooted at `root`, as a list of the parent of
+    /// each cell.
+    fn spanning_tree(&self, root: usize) -> Vec<Option<usize>> {
+        let mut parent = vec![None; self.cells.len()];
+        let mut seen = vec![false; self.cells.len()];
+        seen[root] = true;
+
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            for &n in &self.neighbours[v] {
+                if !seen[n] {
+                    seen[n] = true;
+                    parent[n] = Some(v);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        parent
+    }
+
+    /// Splits the region into blocks (maximal parts of the region that remain connected after
+    /// removing any single cell), using a depth-first search starting from `root`. Each block is
+    /// returned as the cell that is closest to `root`, followed by the other cells.
+    fn blocks(&self, root: usize) -> Vec<Vec<usize>> {
+        let mut discovered = vec![None; self.cells.len()];
+        let mut low = vec![0; self.cells.len()];
+        let mut time = 1;
+        discovered[root] = Some(0);
+
+        let mut stack = vec![root];
+        let mut calls = vec![(root, 0)];
+        let mut blocks = Vec::new();
+
+        while let Some((v, i)) = calls.last_mut() {
+            let v = *v;
+
+            if let Some(&w) = self.neighbours[v].get(*i) {
+                *i += 1;
+
+                if let Some(d) = discovered[w] {
+                    low[v] = low[v].min(d);
+                } else {
+                    discovered[w] = Some(time);
+                    low[w] = time;
+                    time += 1;
+                    stack.push(w);
+                    calls.push((w, 0));
+                }
+            } else {
+                calls.pop();
+
+                if let Some(&(u, _)) = calls.last() {
+                    low[u] = low[u].min(low[v]);
+
+                    // `u` separates `v` from the root, so it is the first cell of a new block
+                    if discovered[u].is_some_and(|d| low[v] >= d) {
+                        let mut block = vec![u];
+                        while let Some(x) = stack.pop() {
+                            block.push(x);
+                            if x == v {
+                                break;
+                            }
+                        }
+                        blocks.push(block);
+                    }
+                }
+            }
+        }
+
+        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::{algorithm::algorithm::Algorithm, puzzle::puzzle::Puzzle};
+
+    fn puzzle(s: &str, obstacles: &[(u64, u64)]) -> ObstaclePuzzle<Puzzle> {
+        ObstaclePuzzle::new(Puzzle::from_str(s).unwrap(), obstacles).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let p = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let size = p.size();
+
+        assert!(ObstaclePuzzle::new(p.clone(), &[(1, 1)]).is_ok());
+        assert_eq!(
+            ObstaclePuzzle::new(p.clone(), &[(3, 1)]),
+            Err(ObstaclePuzzleError::PositionOutOfBounds { size, pos: (3, 1) })
+        );
+        assert_eq!(
+            ObstaclePuzzle::new(p, &[(2, 2)]),
+            Err(ObstaclePuzzleError::GapSolvedPosition)
+        );
+
+        let p = Puzzle::from_str("1 2 3/5 4 6/7 8 0").unwrap();
+        assert_eq!(
+            ObstaclePuzzle::new(p, &[(1, 1)]),
+            Err(ObstaclePuzzleError::PieceNotFixed((1, 1)))
+        );
+    }
+
+    #[test]
+    fn test_obstacles() {
+        let p = puzzle("1 2 3 4/5 6 7 8/9 10 11 12/13 14 15 0", &[(1, 1), (2, 1)]);
+        assert!(p.is_obstacle_xy((1, 1)));
+        assert!(p.is_obstacle(6));
+        assert!(!p.is_obstacle_xy((0, 0)));
+        assert_eq!(p.obstacles().collect::<Vec<_>>(), vec![(1, 1), (2, 1)]);
+        assert_eq!(p.num_movable_pieces(), 13);
+    }
+
+    #[test]
+    fn test_can_move_dir() {
+        let p = puzzle("1 2 3/4 5 6/7 0 8", &[(1, 1)]);
+        assert!(p.can_move_dir(Direction::Left));
+        assert!(p.can_move_dir(Direction::Right));
+        assert!(!p.can_move_dir(Direction::Down));
+        assert!(!p.can_move_dir(Direction::Up));
+    }
+
+    #[test]
+    fn test_apply_move() {
+        let mut p = puzzle("1 2 3/4 5 6/7 8 0", &[(1, 1)]);
+        assert!(p.try_apply_move(Move::new(Direction::Right, 1)));
+        assert!(!p.try_apply_move(Move::new(Direction::Down, 1)));
+        assert!(p.try_apply_move(Move::new(Direction::Right, 1)));
+        assert!(p.try_apply_move(Move::new(Direction::Down, 2)));
+        assert!(!p.try_apply_move(Move::new(Direction::Left, 3)));
+        assert_eq!(p.inner(), &Puzzle::from_str("0 2 3/1 5 6/4 7 8").unwrap());
+    }
+
+    #[test]
+    fn test_move_position() {
+        let mut p = puzzle("1 2 3/4 5 6/7 8 0", &[(2, 1)]);
+        assert!(!p.try_move_position_xy((2, 0)));
+        assert!(p.try_move_position_xy((0, 2)));
+        assert!(p.try_move_position_xy((0, 0)));
+        assert_eq!(p.inner(), &Puzzle::from_str("0 2 3/1 5 6/4 7 8").unwrap());
+    }
+
+    #[test]
+    fn test_is_solution_of() {
+        let mut p = puzzle("1 2 3/4 5 6/7 8 0", &[(1, 1)]);
+        p.apply_alg(&Algorithm::from_str("R2D2L2U2").unwrap());
+
+        let alg = Algorithm::from_str("D2R2U2L2").unwrap();
+        assert!(alg.is_solution_of(p.clone()));
+
+        // This is a solution of the puzzle without the obstacle, but it passes through (1, 1)
+        let alg = Algorithm::from_str("D2R2U2L2RDUL").unwrap();
+        assert!(alg.is_solution_of(p.inner().clone()));
+        assert!(!alg.is_solution_of(p));
+    }
+
+    #[test]
+    fn test_is_solvable() {
+        // Rotations of the ring around the center of a 3x3 puzzle
+        assert!(puzzle("1 2 3/4 5 6/7 8 0", &[(1, 1)]).is_solvable());
+        assert!(puzzle("4 1 2/7 5 3/8 0 6", &[(1, 1)]).is_solvable());
+
+        // Wrong parity
+        assert!(!puzzle("2 1 3/4 5 6/7 8 0", &[(1, 1)]).is_solvable());
+        assert!(!puzzle(
+            "1 2 3 4/5 6 7 8/9 10 11 12/14 13 15 0",
+            &[(1, 1), (2, 1), (1, 2)]
+        )
+        .is_solvable());
+
+        // Position (0, 0) is cut off from the gap, but does not contain its solved piece
+        assert!(!puzzle("6 2 3 4/5 7 1 8/9 10 11 12/13 14 15 0", &[(1, 0), (0, 1)]).is_solvable());
+
+        // The pieces around the ring can only be rotated
+        let p = puzzle("2 3 1/4 5 6/7 8 0", &[(1, 1)]);
+        assert!(p.passes_parity_check());
+        assert!(!p.is_solvable());
+
+        // The free cells form a corridor, so the pieces can't change order
+        let p = puzzle("4 2 6/1 5 3/7 8 0", &[(1, 0), (1, 1)]);
+        assert!(p.passes_parity_check());
+        assert!(!p.is_solvable());
+        assert!(puzzle("1 2 3/4 5 6/7 0 8", &[(1, 0), (1, 1)]).is_solvable());
+
+        // Pieces can't move between the two loops on either side of (2, 1)
+        let p = puzzle("4 2 3 1 5/7 6 8 9 0", &[(2, 0)]);
+        assert!(p.passes_parity_check());
+        assert!(!p.is_solvable());
+
+        let mut p = puzzle("1 2 3 4 5/6 7 8 9 0", &[(2, 0)]);
+        p.apply_alg(&Algorithm::from_str("R3DRUL3").unwrap());
+        assert!(p.is_solvable());
+    }
+}
@@ -12,6 +12,7 @@ use crate::puzzle::{
     label::label::BijectiveLabel,
     size::{Size, SizeError},
     sliding_puzzle::SlidingPuzzle,
+    symmetry::Symmetry,
 };
 
 /// A sliding puzzle, with an implementation of the [`SlidingPuzzle`] trait.
@@ -121,6 +122,39 @@ impl Puzzle {
         Self::with_pieces(pieces, size)
     }
 
+    /// Returns the puzzle obtained by applying `symmetry` to the positions of the pieces.
+    ///
+    /// The gap stays the gap, and the other pieces are relabelled so that solutions carry over: if
+    /// `alg` solves `self`, then `alg.apply_symmetry(symmetry)` followed by
+    /// [`Symmetry::gap_correction`] solves the returned puzzle.
+    #[must_use]
+    pub fn apply_symmetry(&self, symmetry: Symmetry) -> Self {
+        let size = symmetry.transform_size(self.size);
+        let width = self.size.width();
+
+        // The solved state of the new puzzle, with the gap moved to the image of the solved gap
+        // position
+        let mut target = Self::new(size);
+        target.apply_alg(&symmetry.gap_correction(self.size).inverse());
+
+        let mut pieces = vec![0; self.pieces.len()];
+        let mut gap = 0;
+        for (i, &piece) in self.pieces.iter().enumerate() {
+            let i = i as u64;
+            let (x, y) = symmetry.transform_position(self.size, (i % width, i / width));
+            let idx = x + size.width() * y;
+
+            if piece == 0 {
+                gap = idx;
+            } else {
+                let solved = symmetry.transform_position(self.size, self.solved_pos_xy(piece));
+                pieces[idx as usize] = target.piece_at_xy(solved);
+            }
+        }
+
+        Self { pieces, size, gap }
+    }
+
     /// Equivalent to [`DisplayInline::new`].
     #[must_use]
     pub fn display_inline(&self) -> DisplayInline<'_, Self> {
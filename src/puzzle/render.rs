@@ -1,24 +1,56 @@
 //! Defines the [`Renderer`] struct for creating SVG images of [`SlidingPuzzle`]s.
 
-use std::{fmt::Display, ops::Deref};
+pub mod animation;
+pub mod config;
+pub mod grid_lines;
+pub mod layers;
+pub mod overlay;
+#[cfg(feature = "raster")]
+pub mod raster;
+pub mod sheet;
+pub mod terminal;
+
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+    sync::Arc,
+};
 
-use num_traits::Zero as _;
+use itertools::Itertools as _;
+use num_traits::{ToPrimitive as _, Zero as _};
 use palette::rgb::Rgba;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use svg::{
     node::{
-        element::{Group, Rectangle, Style, Text as TextElement},
+        element::{
+            AnimateTransform, ClipPath, Definitions, Group, Image as ImageElement, Line, Polygon,
+            Rectangle, Style, Text as TextElement,
+        },
         Text as TextNode,
     },
     Document,
 };
 use thiserror::Error;
 
-use crate::puzzle::{
-    color_scheme::{Black, ColorScheme},
-    size::Size,
-    sliding_puzzle::SlidingPuzzle,
+#[cfg(feature = "raster")]
+use crate::puzzle::render::raster::RasterError;
+use crate::{
+    algorithm::{algorithm::Algorithm, direction::Direction, r#move::r#move::Move},
+    puzzle::{
+        color_scheme::{multi_layer::MultiLayerColorScheme, Black, ColorScheme},
+        grids::Grids,
+        label::{label::Label, rect_partition::Rect},
+        obstacle::ObstaclePuzzle,
+        render::{
+            animation::AnimationTiming,
+            grid_lines::{GridLines, StrokeStyle},
+            layers::{LayerStyle, Layers},
+            overlay::Overlay,
+        },
+        size::Size,
+        sliding_puzzle::SlidingPuzzle,
+    },
 };
 
 /// Error type for [`Renderer`].
@@ -28,6 +60,10 @@ pub enum RendererError {
     /// Returned when the given puzzle size is incompatible with the label.
     #[error("IncompatibleLabel: puzzle size ({0}) can not be used with the given label")]
     IncompatibleLabel(Size),
+
+    /// Returned when a move of an animated algorithm can not be applied to the puzzle.
+    #[error("IllegalMove: move {0} can not be applied to the puzzle")]
+    IllegalMove(Move),
 }
 
 /// A font that can be used with [`Renderer`].
@@ -51,6 +87,31 @@ pub enum Font<'a> {
     },
 }
 
+/// An image that can be used with [`RendererBuilder::image`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Image<'a> {
+    /// An image defined by a URL (including a local file path or a data URL).
+    Url(&'a str),
+    /// An image defined by base 64 data and an image format, e.g. `png` or `jpeg`.
+    Base64 {
+        /// Base 64 image data.
+        data: &'a str,
+        /// Format of the image data.
+        format: &'a str,
+    },
+}
+
+impl Image<'_> {
+    /// The URL of the image, as used in the `href` attribute of an SVG `image` element.
+    #[must_use]
+    pub fn href(&self) -> String {
+        match self {
+            Self::Url(url) => (*url).to_string(),
+            Self::Base64 { data, format } => format!("data:image/{format};base64,{data}"),
+        }
+    }
+}
+
 /// Struct containing the information needed to draw the borders of the puzzle.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Borders<S: ColorScheme> {
@@ -101,6 +162,88 @@ impl<S: ColorScheme> Borders<S> {
     }
 }
 
+/// The text drawn on each piece of the puzzle.
+///
+/// The default value is [`TextContent::Number`].
+#[derive(Clone, Default)]
+pub enum TextContent<'a> {
+    /// The number of the piece.
+    #[default]
+    Number,
+    /// No text.
+    Blank,
+    /// A letter for each piece: A for piece 1, B for piece 2, ..., Z for piece 26, AA for piece 27,
+    /// etc.
+    Letters,
+    /// The label of the solved position of the piece.
+    Label(Arc<dyn Label + Send + Sync + 'a>),
+    /// The coordinates of the solved position of the piece, in the form `x,y`.
+    SolvedPosition,
+    /// The result of a function called with the piece and its solved position.
+    Custom(Arc<dyn Fn(u64, (u64, u64)) -> String + Send + Sync + 'a>),
+}
+
+impl Debug for TextContent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number => write!(f, "Number"),
+            Self::Blank => write!(f, "Blank"),
+            Self::Letters => write!(f, "Letters"),
+            Self::Label(_) => write!(f, "Label(..)"),
+            Self::SolvedPosition => write!(f, "SolvedPosition"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for TextContent<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number, Self::Number)
+            | (Self::Blank, Self::Blank)
+            | (Self::Letters, Self::Letters)
+            | (Self::SolvedPosition, Self::SolvedPosition) => true,
+            (Self::Label(a), Self::Label(b)) => Arc::ptr_eq(a, b),
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            (
+                Self::Number
+                | Self::Blank
+                | Self::Letters
+                | Self::Label(_)
+                | Self::SolvedPosition
+                | Self::Custom(_),
+                _,
+            ) => false,
+        }
+    }
+}
+
+impl TextContent<'_> {
+    /// The text to draw on `piece`, whose solved position on a puzzle of size `size` is
+    /// `solved_pos`. Returns `None` if there is no text.
+    #[must_use]
+    pub fn text(&self, piece: u64, size: Size, solved_pos: (u64, u64)) -> Option<String> {
+        match self {
+            Self::Number => Some(piece.to_string()),
+            Self::Blank => None,
+            Self::Letters => {
+                // Bijective base 26, so that there is no letter representing 0
+                let mut letters = Vec::new();
+                let mut n = piece;
+                while n > 0 {
+                    n -= 1;
+                    letters.push(char::from(b'A' + (n % 26) as u8));
+                    n /= 26;
+                }
+                Some(letters.into_iter().rev().collect())
+            }
+            Self::Label(label) => Some(label.position_label(size, solved_pos).to_string()),
+            Self::SolvedPosition => Some(format!("{},{}", solved_pos.0, solved_pos.1)),
+            Self::Custom(f) => Some(f(piece, solved_pos)),
+        }
+    }
+}
+
 /// Struct containing the information needed to draw text on the pieces of the puzzle.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Text<'a, S: ColorScheme> {
@@ -108,6 +251,7 @@ pub struct Text<'a, S: ColorScheme> {
     font: Font<'a>,
     font_size: f32,
     position: (f32, f32),
+    content: TextContent<'a>,
 }
 
 impl Text<'_, Black> {
@@ -133,6 +277,7 @@ impl<'a, S: ColorScheme> Text<'a, S> {
             font: Font::Family("sans-serif"),
             font_size: 30.0,
             position: (0.5, 0.5),
+            content: TextContent::Number,
         }
     }
 
@@ -170,6 +315,13 @@ impl<'a, S: ColorScheme> Text<'a, S> {
         self
     }
 
+    /// Set the text drawn on each piece.
+    #[must_use]
+    pub fn content(mut self, content: TextContent<'a>) -> Self {
+        self.content = content;
+        self
+    }
+
     /// Write the formatting options into a CSS string.
     #[must_use]
     pub fn style_string(&self) -> String {
@@ -219,6 +371,21 @@ pub enum SubschemeStyle {
     BorderColor,
 }
 
+/// Ways that the gap can be displayed on the puzzle.
+///
+/// The default value is [`GapStyle::Hidden`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GapStyle {
+    /// Don't draw the gap.
+    #[default]
+    Hidden,
+    /// Draw the gap as an empty tile of the given color.
+    Color(Rgba),
+    /// Draw the gap in the same way as the other pieces.
+    Piece,
+}
+
 /// Used to build a [`Renderer`].
 #[derive(Clone, Debug, PartialEq)]
 pub struct RendererBuilder<
@@ -227,6 +394,8 @@ pub struct RendererBuilder<
     U: ColorScheme = Box<dyn ColorScheme + 'a>,
     T: ColorScheme = Box<dyn ColorScheme + 'a>,
     B: ColorScheme = Box<dyn ColorScheme + 'a>,
+    M: MultiLayerColorScheme = Box<dyn MultiLayerColorScheme + 'a>,
+    G: Grids = Box<dyn Grids + 'a>,
 > {
     scheme: S,
     subscheme: Option<U>,
@@ -238,6 +407,12 @@ pub struct RendererBuilder<
     padding: f32,
     subscheme_style: Option<SubschemeStyle>,
     background_color: Rgba,
+    obstacle_color: Rgba,
+    gap_style: GapStyle,
+    overlays: Vec<Overlay>,
+    layers: Option<Layers<M>>,
+    grid_lines: Option<GridLines<G>>,
+    image: Option<Image<'a>>,
 }
 
 /// Draws a [`SlidingPuzzle`] as an SVG image.
@@ -248,12 +423,21 @@ pub struct Renderer<
     U: ColorScheme = Box<dyn ColorScheme + 'a>,
     T: ColorScheme = Box<dyn ColorScheme + 'a>,
     B: ColorScheme = Box<dyn ColorScheme + 'a>,
->(RendererBuilder<'a, S, U, T, B>);
-
-impl<'a, S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Deref
-    for Renderer<'a, S, U, T, B>
+    M: MultiLayerColorScheme = Box<dyn MultiLayerColorScheme + 'a>,
+    G: Grids = Box<dyn Grids + 'a>,
+>(RendererBuilder<'a, S, U, T, B, M, G>);
+
+impl<
+        'a,
+        S: ColorScheme,
+        U: ColorScheme,
+        T: ColorScheme,
+        B: ColorScheme,
+        M: MultiLayerColorScheme,
+        G: Grids,
+    > Deref for Renderer<'a, S, U, T, B, M, G>
 {
-    type Target = RendererBuilder<'a, S, U, T, B>;
+    type Target = RendererBuilder<'a, S, U, T, B, M, G>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -268,8 +452,15 @@ impl<'a> RendererBuilder<'a> {
     }
 }
 
-impl<'a, S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme>
-    RendererBuilder<'a, S, U, T, B>
+impl<
+        'a,
+        S: ColorScheme,
+        U: ColorScheme,
+        T: ColorScheme,
+        B: ColorScheme,
+        M: MultiLayerColorScheme,
+        G: Grids,
+    > RendererBuilder<'a, S, U, T, B, M, G>
 {
     /// Create a new [`RendererBuilder`].
     #[must_use]
@@ -285,6 +476,12 @@ impl<'a, S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme>
             padding: 0.0,
             subscheme_style: Some(SubschemeStyle::Rectangle),
             background_color: Rgba::new(1.0, 1.0, 1.0, 0.0),
+            obstacle_color: Rgba::new(0.25, 0.25, 0.25, 1.0),
+            gap_style: GapStyle::Hidden,
+            overlays: Vec::new(),
+            layers: None,
+            grid_lines: None,
+            image: None,
         }
     }
 
@@ -358,14 +555,69 @@ impl<'a, S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme>
         self
     }
 
+    /// Set the color used to draw obstacle cells of an [`ObstaclePuzzle`].
+    #[must_use]
+    pub fn obstacle_color(mut self, color: Rgba) -> Self {
+        self.obstacle_color = color;
+        self
+    }
+
+    /// Set the gap style.
+    #[must_use]
+    pub fn gap_style(mut self, style: GapStyle) -> Self {
+        self.gap_style = style;
+        self
+    }
+
+    /// Add an overlay. Overlays are drawn on top of the pieces, in the order they were added.
+    #[must_use]
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Set the layers of a [`MultiLayerColorScheme`] to draw on each piece. The layers are drawn
+    /// on top of the color from the main color scheme, and below the text.
+    #[must_use]
+    pub fn layers(mut self, layers: Layers<M>) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Set the grid lines, which draw the boundaries of the grids of one or more [`Grids`] over
+    /// the puzzle, e.g. to show the blocks of a block-building method. The grid lines are drawn
+    /// on top of the pieces, and below the overlays.
+    #[must_use]
+    pub fn grid_lines(mut self, grid_lines: GridLines<G>) -> Self {
+        self.grid_lines = Some(grid_lines);
+        self
+    }
+
+    /// Set an image to draw on the pieces, making a picture puzzle. The image is scaled to cover
+    /// the whole puzzle, and each piece shows the part of the image at its solved position. The
+    /// image is drawn on top of the color from the main color scheme, and inside the borders.
+    #[must_use]
+    pub fn image(mut self, image: Image<'a>) -> Self {
+        self.image = Some(image);
+        self
+    }
+
     /// Builds a [`Renderer`].
     #[must_use]
-    pub fn build(self) -> Renderer<'a, S, U, T, B> {
+    pub fn build(self) -> Renderer<'a, S, U, T, B, M, G> {
         Renderer(self)
     }
 }
 
-impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_, S, U, T, B> {
+impl<
+        S: ColorScheme,
+        U: ColorScheme,
+        T: ColorScheme,
+        B: ColorScheme,
+        M: MultiLayerColorScheme,
+        G: Grids,
+    > Renderer<'_, S, U, T, B, M, G>
+{
     /// Returns the CSS string used to style the image.
     pub fn style_string(&self) -> String {
         let font = self
@@ -379,11 +631,7 @@ impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_
             format!("#{color:x}")
         };
 
-        let border_thickness = self
-            .borders
-            .as_ref()
-            .map(|a| a.thickness)
-            .unwrap_or_default();
+        let border_thickness = self.border_thickness();
 
         format!(
             "svg {{ background-color: {bg}; }} \
@@ -411,7 +659,17 @@ impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_
         )
     }
 
+    /// Returns the CSS string used to style the obstacle cells drawn by
+    /// [`Renderer::obstacle_group`], in addition to [`Renderer::style_string`].
+    pub fn obstacle_style_string(&self) -> String {
+        let color: Rgba<_, u8> = self.obstacle_color.into_format();
+        format!("rect.obstacle {{ fill: #{color:x}; }}")
+    }
+
     /// Draws `puzzle` as an SVG image, wrapped in an SVG group element.
+    ///
+    /// The group may refer to [`Renderer::definitions`], which must be added once to any document
+    /// that contains it.
     pub fn group<Puzzle>(&self, puzzle: &Puzzle) -> Result<Group, RendererError>
     where
         Puzzle: SlidingPuzzle,
@@ -424,45 +682,431 @@ impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_
 
         for y in 0..height {
             for x in 0..width {
-                let piece = puzzle.piece_at_xy((x, y));
-
-                if piece != Puzzle::Piece::zero() {
-                    group = group.add(self.render_piece(puzzle, (x, y)));
+                if let Some(cell) = self.render_cell(puzzle, (x, y)) {
+                    group = group.add(cell);
                 }
             }
         }
 
+        if let Some(boundaries) = self.layer_boundaries_group(size) {
+            group = group.add(boundaries);
+        }
+
+        if let Some(grid_lines) = self.grid_lines_group(size) {
+            group = group.add(grid_lines);
+        }
+
+        if !self.overlays.is_empty() {
+            group = group.add(self.overlay_group(puzzle)?);
+        }
+
         Ok(group)
     }
 
-    /// Draws the piece of `puzzle` at position `(x, y)` as an SVG image, wrapped in an SVG group
-    /// element.
-    pub fn render_piece<Puzzle>(&self, puzzle: &Puzzle, (x, y): (u64, u64)) -> Group
+    /// Draws an [`ObstaclePuzzle`] as an SVG image, wrapped in an SVG group element. Obstacle
+    /// cells are drawn as solid blocks using the obstacle color, instead of as pieces.
+    ///
+    /// The group may refer to [`Renderer::definitions`], which must be added once to any document
+    /// that contains it. The obstacle color is set by [`Renderer::obstacle_style_string`].
+    pub fn obstacle_group<Puzzle>(
+        &self,
+        puzzle: &ObstaclePuzzle<Puzzle>,
+    ) -> Result<Group, RendererError>
     where
         Puzzle: SlidingPuzzle,
         Puzzle::Piece: Display,
     {
         let size = puzzle.size();
+        let (width, height) = size.into();
 
-        let border_thickness = self
-            .borders
-            .as_ref()
-            .map(|a| a.thickness)
-            .unwrap_or_default();
+        let mut group = Group::new();
 
+        for y in 0..height {
+            for x in 0..width {
+                if puzzle.is_obstacle_xy((x, y)) {
+                    let (px, py) = self.piece_position((x, y));
+                    let half_border = self.border_thickness() / 2.0;
+
+                    group = group.add(
+                        Rectangle::new()
+                            .set("x", px - half_border)
+                            .set("y", py - half_border)
+                            .set("width", self.tile_size + self.border_thickness())
+                            .set("height", self.tile_size + self.border_thickness())
+                            .set("class", "obstacle"),
+                    );
+                } else if let Some(cell) = self.render_cell(puzzle.inner(), (x, y)) {
+                    group = group.add(cell);
+                }
+            }
+        }
+
+        if let Some(boundaries) = self.layer_boundaries_group(size) {
+            group = group.add(boundaries);
+        }
+
+        if let Some(grid_lines) = self.grid_lines_group(size) {
+            group = group.add(grid_lines);
+        }
+
+        if !self.overlays.is_empty() {
+            group = group.add(self.overlay_group(puzzle)?);
+        }
+
+        Ok(group)
+    }
+
+    /// Definitions used by the pieces, wrapped in an SVG defs element. Returns `None` if nothing
+    /// needs to be defined.
+    ///
+    /// The ids of the definitions are derived from the tile geometry, so documents containing
+    /// groups from several renderers only have conflicting ids when the definitions are the same.
+    #[must_use]
+    pub fn definitions(&self) -> Option<Definitions> {
+        self.image.as_ref()?;
+
+        // Clip path of a single tile (inside the borders), relative to the top left of the tile
+        let half_border = self.border_thickness() / 2.0;
+        let rounding = (self.tile_rounding - half_border).max(0.0);
+
+        Some(
+            Definitions::new().add(
+                ClipPath::new().set("id", self.clip_path_id()).add(
+                    Rectangle::new()
+                        .set("x", half_border)
+                        .set("y", half_border)
+                        .set("width", (self.tile_size - 2.0 * half_border).max(0.0))
+                        .set("height", (self.tile_size - 2.0 * half_border).max(0.0))
+                        .set("rx", rounding)
+                        .set("ry", rounding),
+                ),
+            ),
+        )
+    }
+
+    /// The id of the clip path of a single tile in [`Renderer::definitions`].
+    fn clip_path_id(&self) -> String {
+        format!(
+            "tile-clip-{}-{}-{}",
+            self.tile_size,
+            self.border_thickness(),
+            self.tile_rounding
+        )
+    }
+
+    /// An SVG document of the size needed to draw a puzzle of size `size`, containing the CSS
+    /// string `style`, the definitions and `group`.
+    fn document(&self, size: Size, style: String, group: Group) -> Document {
+        let (image_w, image_h) = self.image_size(size);
+
+        let mut doc = Document::new().add(Style::new(style));
+        if let Some(defs) = self.definitions() {
+            doc = doc.add(defs);
+        }
+
+        doc.add(group).set("width", image_w).set("height", image_h)
+    }
+
+    /// Draws the piece or gap at position `(x, y)`, taking into account the gap style and any
+    /// [`Overlay::Dim`] and [`Overlay::DimSolved`] overlays. Returns `None` if nothing is drawn.
+    fn render_cell<Puzzle>(&self, puzzle: &Puzzle, (x, y): (u64, u64)) -> Option<Group>
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
         let piece = puzzle.piece_at_xy((x, y));
-        let solved_pos = puzzle.solved_pos_xy(piece);
 
+        let is_gap = piece == Puzzle::Piece::zero();
+        let group = match self.gap_style {
+            GapStyle::Hidden if is_gap => return None,
+            GapStyle::Color(color) if is_gap => {
+                let (px, py) = self.piece_position((x, y));
+                let color: Rgba<_, u8> = color.into_format();
+
+                Group::new().add(
+                    Rectangle::new()
+                        .set("x", px)
+                        .set("y", py)
+                        .set("width", self.tile_size)
+                        .set("height", self.tile_size)
+                        .set("rx", self.tile_rounding)
+                        .set("ry", self.tile_rounding)
+                        .set("class", "gap")
+                        .set("fill", format!("#{color:x}")),
+                )
+            }
+            GapStyle::Hidden | GapStyle::Color(_) | GapStyle::Piece => {
+                self.render_piece(puzzle, (x, y))
+            }
+        };
+
+        let opacity = self
+            .overlays
+            .iter()
+            .filter_map(|overlay| overlay.dim_opacity(puzzle, (x, y)))
+            .reduce(f32::min);
+
+        Some(match opacity {
+            Some(opacity) => group.set("opacity", opacity),
+            None => group,
+        })
+    }
+
+    /// Draws the boundaries of the grids of each layer of the [`Layers`], wrapped in an SVG group
+    /// element. Returns `None` if the boundaries are not drawn.
+    fn layer_boundaries_group(&self, size: Size) -> Option<Group> {
+        let layers = self.layers.as_ref()?;
+        let mut group = Group::new().set("class", "layer-boundaries");
+
+        for layer in 0..layers.scheme().num_layers(size) {
+            let (color, thickness) = layers.boundary_style(layer)?;
+            let style = StrokeStyle::new(color).thickness(thickness);
+
+            for outline in
+                self.grid_outlines(size, |pos| layers.grid_containing_pos(size, layer, pos))
+            {
+                group = group.add(style.apply(outline));
+            }
+        }
+
+        Some(group)
+    }
+
+    /// Draws the boundaries of the grids of each level of the [`GridLines`], wrapped in an SVG
+    /// group element. Returns `None` if there are no grid lines.
+    fn grid_lines_group(&self, size: Size) -> Option<Group> {
+        let grid_lines = self.grid_lines.as_ref()?;
+
+        let mut group = Group::new().set("class", "grid-lines");
+
+        for (grids, style) in grid_lines.levels() {
+            for outline in
+                self.grid_outlines(size, |pos| Some(grids.grid_containing_pos(size, pos)))
+            {
+                group = group.add(style.apply(outline));
+            }
+        }
+
+        Some(group)
+    }
+
+    /// Draws the [`Overlay::Highlight`] and [`Overlay::NextMoves`] overlays, wrapped in an SVG
+    /// group element.
+    fn overlay_group<Puzzle>(&self, puzzle: &Puzzle) -> Result<Group, RendererError>
+    where
+        Puzzle: SlidingPuzzle,
+    {
+        let (width, height) = puzzle.size().into();
+
+        let mut group = Group::new().set("class", "overlay");
+
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Highlight {
+                    pieces,
+                    color,
+                    thickness,
+                } => {
+                    let stroke = {
+                        let color: Rgba<_, u8> = color.into_format();
+                        format!("#{color:x}")
+                    };
+
+                    for (y, x) in (0..height).cartesian_product(0..width) {
+                        let is_highlighted = puzzle
+                            .piece_at_xy((x, y))
+                            .to_u64()
+                            .is_some_and(|piece| pieces.contains(&piece));
+
+                        if is_highlighted {
+                            let (px, py) = self.piece_position((x, y));
+
+                            group = group.add(
+                                Rectangle::new()
+                                    .set("x", px)
+                                    .set("y", py)
+                                    .set("width", self.tile_size)
+                                    .set("height", self.tile_size)
+                                    .set("rx", self.tile_rounding)
+                                    .set("ry", self.tile_rounding)
+                                    .set("class", "highlight")
+                                    .set("fill", "none")
+                                    .set("stroke", stroke.as_str())
+                                    .set("stroke-width", *thickness),
+                            );
+                        }
+                    }
+                }
+                Overlay::NextMoves {
+                    algorithm,
+                    count,
+                    color,
+                } => {
+                    let fill = {
+                        let color: Rgba<_, u8> = color.into_format();
+                        format!("#{color:x}")
+                    };
+
+                    let (mut gx, mut gy) = puzzle.gap_position_xy();
+                    let mut moves = Algorithm::new();
+
+                    for &mv in algorithm.moves().iter().take(*count) {
+                        // Check the moves with the puzzle itself rather than only the bounds of
+                        // the board, so that arrows never point into an obstacle
+                        moves.push(mv);
+                        if !puzzle.can_apply_alg(&moves) {
+                            return Err(RendererError::IllegalMove(mv));
+                        }
+
+                        // A move with amount 0 does not move the gap, so there is no arrow to draw
+                        let n = mv.amount;
+                        if n == 0 {
+                            continue;
+                        }
+
+                        // The gap moves in the opposite direction to the pieces
+                        let new_gap = match mv.direction {
+                            Direction::Up => (gx, gy + n),
+                            Direction::Left => (gx + n, gy),
+                            Direction::Down => (gx, gy - n),
+                            Direction::Right => (gx - n, gy),
+                        };
+
+                        group = group.add(self.render_arrow(new_gap, (gx, gy), &fill));
+                        (gx, gy) = new_gap;
+                    }
+                }
+                Overlay::Dim { .. } | Overlay::DimSolved { .. } => {}
+            }
+        }
+
+        Ok(group)
+    }
+
+    /// Draws an arrow from the center of the tile at position `from` to the center of the tile at
+    /// position `to`, wrapped in an SVG group element.
+    fn render_arrow(&self, from: (u64, u64), to: (u64, u64), fill: &str) -> Group {
+        let half_tile = self.tile_size / 2.0;
+        let (x1, y1) = self.piece_position(from);
+        let (x2, y2) = self.piece_position(to);
+        let (x1, y1, x2, y2) = (
+            x1 + half_tile,
+            y1 + half_tile,
+            x2 + half_tile,
+            y2 + half_tile,
+        );
+
+        // Unit vector along the arrow, and perpendicular to it
+        let length = (x2 - x1).hypot(y2 - y1);
+        let (ux, uy) = ((x2 - x1) / length, (y2 - y1) / length);
+        let (vx, vy) = (-uy, ux);
+
+        let head_length = self.tile_size * 0.3;
+        let head_width = self.tile_size * 0.15;
+        let (bx, by) = (x2 - ux * head_length, y2 - uy * head_length);
+
+        Group::new()
+            .set("class", "arrow")
+            .add(
+                Line::new()
+                    .set("x1", x1)
+                    .set("y1", y1)
+                    .set("x2", bx)
+                    .set("y2", by)
+                    .set("stroke", fill)
+                    .set("stroke-width", self.tile_size * 0.08),
+            )
+            .add(Polygon::new().set("fill", fill).set(
+                "points",
+                format!(
+                    "{x2},{y2} {},{} {},{}",
+                    bx + vx * head_width,
+                    by + vy * head_width,
+                    bx - vx * head_width,
+                    by - vy * head_width,
+                ),
+            ))
+    }
+
+    fn border_thickness(&self) -> f32 {
+        self.borders
+            .as_ref()
+            .map(|a| a.thickness)
+            .unwrap_or_default()
+    }
+
+    /// Position of the top left corner of the piece at position `(x, y)` in the image.
+    fn piece_position(&self, (x, y): (u64, u64)) -> (f32, f32) {
+        let border_thickness = self.border_thickness();
         let (x, y) = (x as f32, y as f32);
 
-        let rect_pos = (
+        (
             self.padding
                 + border_thickness / 2.0
                 + (self.tile_size + self.tile_gap + border_thickness) * x,
             self.padding
                 + border_thickness / 2.0
                 + (self.tile_size + self.tile_gap + border_thickness) * y,
-        );
+        )
+    }
+
+    /// Outlines of the distinct grids returned by `grid` for the positions of a puzzle of size
+    /// `size`. Each outline is halfway between the pieces inside the grid and the pieces next to
+    /// it.
+    fn grid_outlines<F>(&self, size: Size, grid: F) -> Vec<Rectangle>
+    where
+        F: Fn((u64, u64)) -> Option<Rect>,
+    {
+        let (width, height) = size.into();
+        let half_spacing = f32::midpoint(self.tile_gap, self.border_thickness());
+
+        (0..height)
+            .cartesian_product(0..width)
+            .filter_map(|(y, x)| grid((x, y)))
+            .unique()
+            .map(|rect| {
+                let (x1, y1) = self.piece_position((rect.left(), rect.top()));
+                let (x2, y2) = self.piece_position((rect.right(), rect.bottom()));
+
+                Rectangle::new()
+                    .set("x", x1 - half_spacing)
+                    .set("y", y1 - half_spacing)
+                    .set("width", x2 - x1)
+                    .set("height", y2 - y1)
+            })
+            .collect()
+    }
+
+    /// Size of the image used to draw a puzzle of size `size`.
+    fn image_size(&self, size: Size) -> (f32, f32) {
+        let border_thickness = self.border_thickness();
+
+        let (w, h) = (size.width() as f32, size.height() as f32);
+        (
+            w * self.tile_size
+                + (w - 1.0) * self.tile_gap
+                + w * border_thickness
+                + 2.0 * self.padding,
+            h * self.tile_size
+                + (h - 1.0) * self.tile_gap
+                + h * border_thickness
+                + 2.0 * self.padding,
+        )
+    }
+
+    /// Draws the piece of `puzzle` at position `(x, y)` as an SVG image, wrapped in an SVG group
+    /// element.
+    pub fn render_piece<Puzzle>(&self, puzzle: &Puzzle, (x, y): (u64, u64)) -> Group
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
+        let size = puzzle.size();
+
+        let piece = puzzle.piece_at_xy((x, y));
+        let solved_pos = puzzle.solved_pos_xy(piece);
+
+        let rect_pos = self.piece_position((x, y));
 
         let subscheme_color = self
             .subscheme
@@ -496,26 +1140,67 @@ impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_
             let mut r = Rectangle::new()
                 .set("x", rect_pos.0)
                 .set("y", rect_pos.1)
+                .set("width", self.tile_size)
+                .set("height", self.tile_size)
+                .set("rx", self.tile_rounding)
+                .set("ry", self.tile_rounding)
                 .set("class", "piece")
                 .set("fill", fill);
 
             if let Some(s) = &self.borders {
                 let stroke = color!(s.scheme, SubschemeStyle::BorderColor);
-                r = r.set("stroke", stroke);
+                r = r
+                    .set("stroke", stroke)
+                    .set("stroke-width", self.border_thickness());
             }
 
             r
         };
 
-        let text = self.text.as_ref().map(|text| {
+        let image = self.image.as_ref().map(|image| {
+            // The image covers the whole puzzle, and is shifted so that the part of the image at
+            // the solved position of the piece is inside the tile
+            let (width, height) = size.into();
+            let (x1, y1) = self.piece_position((0, 0));
+            let (x2, y2) = self.piece_position((width - 1, height - 1));
+            let (sx, sy) = self.piece_position(solved_pos);
+
+            Group::new()
+                .set(
+                    "transform",
+                    format!("translate({},{})", rect_pos.0, rect_pos.1),
+                )
+                .add(
+                    ImageElement::new()
+                        .set("href", image.href())
+                        .set("x", x1 - sx)
+                        .set("y", y1 - sy)
+                        .set("width", x2 - x1 + self.tile_size)
+                        .set("height", y2 - y1 + self.tile_size)
+                        .set("preserveAspectRatio", "xMidYMid slice")
+                        .set("clip-path", format!("url(#{})", self.clip_path_id())),
+                )
+        });
+
+        let layers = self
+            .layers
+            .as_ref()
+            .map(|layers| self.render_layers(layers, size, solved_pos, rect_pos));
+
+        let text = self.text.as_ref().and_then(|text| {
+            let content =
+                text.content
+                    .text(piece.to_u64().unwrap_or_default(), size, solved_pos)?;
             let fill = color!(text.scheme, SubschemeStyle::TextColor);
             let (tx, ty) = text.position;
 
-            TextElement::new("")
-                .set("x", rect_pos.0 + self.tile_size * tx)
-                .set("y", rect_pos.1 + self.tile_size * ty)
-                .set("fill", fill)
-                .add(TextNode::new(piece.to_string()))
+            Some(
+                TextElement::new("")
+                    .set("x", rect_pos.0 + self.tile_size * tx)
+                    .set("y", rect_pos.1 + self.tile_size * ty)
+                    .set("fill", fill)
+                    .add(TextNode::new(content)),
+            )
         });
 
         let subscheme_render = subscheme_color
@@ -531,12 +1216,22 @@ impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_
                 Rectangle::new()
                     .set("x", rect_pos.0 + self.tile_size * subrect_pos.0)
                     .set("y", rect_pos.1 + self.tile_size * subrect_pos.1)
+                    .set("width", self.tile_size * 0.7)
+                    .set("height", self.tile_size * 0.1)
                     .set("class", "sub")
                     .set("fill", fill)
             });
 
         let mut group = Group::new().add(rect);
 
+        if let Some(image) = image {
+            group = group.add(image);
+        }
+
+        if let Some(layers) = layers {
+            group = group.add(layers);
+        }
+
         if let Some(text) = text {
             group = group.add(text);
         }
@@ -548,41 +1243,666 @@ impl<S: ColorScheme, U: ColorScheme, T: ColorScheme, B: ColorScheme> Renderer<'_
         group
     }
 
+    /// Draws the layers of `layers` for the piece whose solved position is `solved_pos`, on top of
+    /// the tile whose top left corner is at `rect_pos`, wrapped in an SVG group element.
+    fn render_layers(
+        &self,
+        layers: &Layers<M>,
+        size: Size,
+        solved_pos: (u64, u64),
+        rect_pos: (f32, f32),
+    ) -> Group {
+        let num_layers = layers.scheme().num_layers(size);
+        let num_stripes = (0..num_layers)
+            .filter(|&layer| layers.style(layer) == LayerStyle::Stripe)
+            .count();
+
+        // Layers are drawn inside the borders, so that they don't cover them
+        let half_border = self.border_thickness() / 2.0;
+        let stripe_height = (self.tile_size - 2.0 * half_border) / num_stripes.max(1) as f32;
+
+        let mut group = Group::new().set("class", "layers");
+        let mut stripe = 0;
+
+        for layer in 0..num_layers {
+            let fill = {
+                let color = layers.scheme().color(size, solved_pos, layer);
+                let color: Rgba<_, u8> = color.into_format();
+                format!("#{color:x}")
+            };
+
+            let rect = match layers.style(layer) {
+                LayerStyle::Inset(fraction) => {
+                    let inset = (self.tile_size * fraction).max(half_border);
+                    let rounding = (self.tile_rounding - inset).max(0.0);
+
+                    Rectangle::new()
+                        .set("x", rect_pos.0 + inset)
+                        .set("y", rect_pos.1 + inset)
+                        .set("width", (self.tile_size - 2.0 * inset).max(0.0))
+                        .set("height", (self.tile_size - 2.0 * inset).max(0.0))
+                        .set("rx", rounding)
+                        .set("ry", rounding)
+                }
+                LayerStyle::Stripe => {
+                    let y = rect_pos.1 + half_border + stripe_height * stripe as f32;
+                    stripe += 1;
+
+                    Rectangle::new()
+                        .set("x", rect_pos.0 + half_border)
+                        .set("y", y)
+                        .set("width", self.tile_size - 2.0 * half_border)
+                        .set("height", stripe_height)
+                }
+                LayerStyle::Hidden => continue,
+            };
+
+            group = group.add(rect.set("class", "layer").set("fill", fill));
+        }
+
+        group
+    }
+
     /// Draws `puzzle` as an SVG image.
     pub fn render<Puzzle>(&self, puzzle: &Puzzle) -> Result<Document, RendererError>
     where
         Puzzle: SlidingPuzzle,
         Puzzle::Piece: Display,
+    {
+        Ok(self.document(puzzle.size(), self.style_string(), self.group(puzzle)?))
+    }
+
+    /// The positions of each piece of `puzzle` after each move of `alg`, indexed by the starting
+    /// position index of the piece (`x + width * y`). The first element of each list is the
+    /// starting position.
+    fn piece_paths<Puzzle>(
+        puzzle: &Puzzle,
+        alg: &Algorithm,
+    ) -> Result<Vec<Vec<(u64, u64)>>, RendererError>
+    where
+        Puzzle: SlidingPuzzle + Clone,
     {
         let size = puzzle.size();
         let (width, height) = size.into();
+        let index = |(x, y): (u64, u64)| (x + width * y) as usize;
 
-        let border_thickness = self
-            .borders
-            .as_ref()
-            .map(|a| a.thickness)
-            .unwrap_or_default();
+        // `cells[i]` is the starting position index of the piece currently at position index `i`
+        let mut cells = (0..size.area() as usize).collect::<Vec<_>>();
 
-        let (w, h) = (width as f32, height as f32);
-        let (image_w, image_h) = (
-            w * self.tile_size
-                + (w - 1.0) * self.tile_gap
-                + w * border_thickness
-                + 2.0 * self.padding,
-            h * self.tile_size
-                + (h - 1.0) * self.tile_gap
-                + h * border_thickness
-                + 2.0 * self.padding,
+        let mut paths = (0..height)
+            .flat_map(|y| (0..width).map(move |x| vec![(x, y)]))
+            .collect::<Vec<_>>();
+
+        let mut state = puzzle.clone();
+        for &mv in alg.moves() {
+            for _ in 0..mv.amount {
+                if !state.can_move_dir(mv.direction) {
+                    return Err(RendererError::IllegalMove(mv));
+                }
+
+                let gap = state.gap_position_xy();
+                state.move_dir(mv.direction);
+                cells.swap(index(gap), index(state.gap_position_xy()));
+            }
+
+            for (i, &cell) in cells.iter().enumerate() {
+                let i = i as u64;
+                paths[cell].push((i % width, i / width));
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Draws an animation of `alg` being applied to `puzzle` as an SVG image.
+    ///
+    /// Each piece is drawn once, at its position in `puzzle`, and moved using an SVG
+    /// `animateTransform` element so that it slides along with the moves of `alg`. Every move
+    /// (including multi-tile moves) takes the same amount of time. See [`AnimationTiming`] for the
+    /// options.
+    pub fn render_animation<Puzzle>(
+        &self,
+        puzzle: &Puzzle,
+        alg: &Algorithm,
+        timing: &AnimationTiming,
+    ) -> Result<Document, RendererError>
+    where
+        Puzzle: SlidingPuzzle + Clone,
+        Puzzle::Piece: Display,
+    {
+        let size = puzzle.size();
+        let (width, height) = size.into();
+        let paths = Self::piece_paths(puzzle, alg)?;
+
+        let total = timing.total_duration(alg.moves().len());
+
+        let mut key_times = (0..=alg.moves().len())
+            .map(|i| timing.move_duration * i as f32 / total)
+            .collect::<Vec<_>>();
+        if timing.end_delay > 0.0 {
+            key_times.push(1.0);
+        }
+
+        let mut group = Group::new();
+
+        for (y, x) in (0..height).cartesian_product(0..width) {
+            if puzzle.piece_at_xy((x, y)) == Puzzle::Piece::zero() {
+                continue;
+            }
+
+            let mut piece = Group::new().add(self.render_piece(puzzle, (x, y)));
+
+            let path = &paths[(x + width * y) as usize];
+            if total > 0.0 && path.iter().any(|&pos| pos != (x, y)) {
+                let (sx, sy) = self.piece_position((x, y));
+                let values = path
+                    .iter()
+                    .chain(path.last().filter(|_| timing.end_delay > 0.0))
+                    .map(|&pos| {
+                        let (px, py) = self.piece_position(pos);
+                        format!("{},{}", px - sx, py - sy)
+                    })
+                    .collect::<Vec<_>>();
+
+                piece = piece.add(
+                    AnimateTransform::new()
+                        .set("attributeName", "transform")
+                        .set("type", "translate")
+                        .set("values", values.join(";"))
+                        .set("keyTimes", key_times.iter().join(";"))
+                        .set("calcMode", "spline")
+                        .set(
+                            "keySplines",
+                            vec![timing.easing.key_spline(); key_times.len() - 1].join(";"),
+                        )
+                        .set("dur", format!("{total}s"))
+                        .set(
+                            "repeatCount",
+                            if timing.looping { "indefinite" } else { "1" },
+                        )
+                        .set("fill", "freeze"),
+                );
+            }
+
+            group = group.add(piece);
+        }
+
+        Ok(self.document(size, self.style_string(), group))
+    }
+
+    /// Draws a single frame of an animation of `alg` being applied to `puzzle`, at time `time`
+    /// seconds after the start of the animation. See [`Renderer::render_animation`].
+    pub fn render_animation_frame<Puzzle>(
+        &self,
+        puzzle: &Puzzle,
+        alg: &Algorithm,
+        timing: &AnimationTiming,
+        time: f32,
+    ) -> Result<Document, RendererError>
+    where
+        Puzzle: SlidingPuzzle + Clone,
+        Puzzle::Piece: Display,
+    {
+        let paths = Self::piece_paths(puzzle, alg)?;
+        Ok(self.frame(puzzle, &paths, timing, time))
+    }
+
+    /// Draws `puzzle` with each piece moved part of the way along its path (see
+    /// [`Renderer::piece_paths`]), as it is at time `time` seconds into the animation.
+    fn frame<Puzzle>(
+        &self,
+        puzzle: &Puzzle,
+        paths: &[Vec<(u64, u64)>],
+        timing: &AnimationTiming,
+        time: f32,
+    ) -> Document
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
+        let size = puzzle.size();
+        let (width, height) = size.into();
+
+        // Index of the move being made at time `time`, and how far through the move we are
+        let (move_idx, t) = if timing.move_duration > 0.0 {
+            let moves = time.max(0.0) / timing.move_duration;
+            (moves.floor() as usize, timing.easing.apply(moves.fract()))
+        } else {
+            (usize::MAX, 0.0)
+        };
+
+        let mut group = Group::new();
+
+        for (y, x) in (0..height).cartesian_product(0..width) {
+            if puzzle.piece_at_xy((x, y)) == Puzzle::Piece::zero() {
+                continue;
+            }
+
+            let path = &paths[(x + width * y) as usize];
+            let (sx, sy) = self.piece_position((x, y));
+            let (ax, ay) = self.piece_position(path[move_idx.min(path.len() - 1)]);
+            let (bx, by) =
+                self.piece_position(path[move_idx.saturating_add(1).min(path.len() - 1)]);
+            let (dx, dy) = (ax + (bx - ax) * t - sx, ay + (by - ay) * t - sy);
+
+            group = group.add(
+                Group::new()
+                    .set("transform", format!("translate({dx},{dy})"))
+                    .add(self.render_piece(puzzle, (x, y))),
+            );
+        }
+
+        self.document(size, self.style_string(), group)
+    }
+
+    /// Draws `puzzle` as a PNG image, and returns the encoded bytes.
+    ///
+    /// Requires the `raster` feature.
+    #[cfg(feature = "raster")]
+    pub fn render_png<Puzzle>(&self, puzzle: &Puzzle) -> Result<Vec<u8>, RasterError>
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
+        raster::encode_png(&raster::rasterize(
+            &self.render(puzzle)?,
+            self.background_color,
+        )?)
+    }
+
+    /// Draws an animation of `alg` being applied to `puzzle` as an animated GIF image, with
+    /// `frame_rate` frames per second, and returns the encoded bytes. See
+    /// [`Renderer::render_animation`].
+    ///
+    /// Requires the `raster` feature.
+    #[cfg(feature = "raster")]
+    pub fn render_gif<Puzzle>(
+        &self,
+        puzzle: &Puzzle,
+        alg: &Algorithm,
+        timing: &AnimationTiming,
+        frame_rate: f32,
+    ) -> Result<Vec<u8>, RasterError>
+    where
+        Puzzle: SlidingPuzzle + Clone,
+        Puzzle::Piece: Display,
+    {
+        let paths = Self::piece_paths(puzzle, alg)?;
+
+        // GIF frame delays are in hundredths of a second
+        let frames_per_move = (timing.move_duration * frame_rate).round().max(1.0);
+        let frame_duration = timing.move_duration / frames_per_move;
+        let delay = (frame_duration * 100.0).round() as u16;
+        let end_delay = ((frame_duration + timing.end_delay) * 100.0).round() as u16;
+
+        let num_frames = alg.moves().len() * frames_per_move as usize;
+        let mut frames = Vec::with_capacity(num_frames + 1);
+        for i in 0..num_frames {
+            let doc = self.frame(puzzle, &paths, timing, i as f32 * frame_duration);
+            frames.push((raster::rasterize(&doc, self.background_color)?, delay));
+        }
+
+        let doc = self.frame(
+            puzzle,
+            &paths,
+            timing,
+            timing.total_duration(alg.moves().len()),
         );
+        frames.push((raster::rasterize(&doc, self.background_color)?, end_delay));
 
-        let style_str = self.style_string();
+        raster::encode_gif(&frames, timing.looping)
+    }
 
-        let doc = Document::new()
-            .add(Style::new(style_str))
-            .add(self.group(puzzle)?)
-            .set("width", image_w)
-            .set("height", image_h);
+    /// Draws an [`ObstaclePuzzle`] as an SVG image.
+    pub fn render_obstacle_puzzle<Puzzle>(
+        &self,
+        puzzle: &ObstaclePuzzle<Puzzle>,
+    ) -> Result<Document, RendererError>
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
+        let style = format!("{} {}", self.style_string(), self.obstacle_style_string());
+        Ok(self.document(puzzle.size(), style, self.obstacle_group(puzzle)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::puzzle::{
+        color_scheme::{
+            balanced_split::{BalancedSplitScheme, Splitting},
+            Scheme,
+        },
+        coloring::Rainbow,
+        label::{
+            grid::Grid,
+            label::{RowGrids, Rows},
+        },
+        puzzle::Puzzle,
+    };
+
+    fn renderer() -> Renderer<'static> {
+        RendererBuilder::with_dyn_scheme(Box::new(Black)).build()
+    }
+
+    #[test]
+    fn test_render_animation() {
+        let puzzle = Puzzle::new(Size::new(3, 3).unwrap());
+        let alg = Algorithm::from_str("DR").unwrap();
+        let doc = renderer()
+            .render_animation(&puzzle, &alg, &AnimationTiming::new().looping(true))
+            .unwrap()
+            .to_string();
+
+        // Only the two pieces that move are animated
+        assert_eq!(doc.matches("<animateTransform").count(), 2);
+        assert!(doc.contains(r#"values="0,0;0,75;0,75""#));
+        assert!(doc.contains(r#"values="0,0;0,0;75,0""#));
+        assert!(doc.contains(r#"keyTimes="0;0.5;1""#));
+        assert!(doc.contains(r#"repeatCount="indefinite""#));
+    }
+
+    #[test]
+    fn test_render_animation_illegal_move() {
+        let puzzle = Puzzle::new(Size::new(3, 3).unwrap());
+        let alg = Algorithm::from_str("DRU2").unwrap();
+        assert_eq!(
+            renderer()
+                .render_animation(&puzzle, &alg, &AnimationTiming::new())
+                .map(|doc| doc.to_string()),
+            Err(RendererError::IllegalMove(Move::from_str("U2").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_gap_style() {
+        let puzzle = Puzzle::new(Size::new(3, 3).unwrap());
+
+        let doc = renderer().render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="piece""#).count(), 8);
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .gap_style(GapStyle::Color(Rgba::new(1.0, 0.0, 0.0, 1.0)))
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="piece""#).count(), 8);
+        assert!(doc.contains(r##"class="gap" fill="#ff0000ff""##));
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .gap_style(GapStyle::Piece)
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="piece""#).count(), 9);
+    }
+
+    #[test]
+    fn test_overlays() {
+        let puzzle = Puzzle::from_str("1 2 3/4 5 6/7 0 8").unwrap();
+        let color = Rgba::new(0.0, 0.0, 1.0, 1.0);
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .overlay(Overlay::Highlight {
+                pieces: vec![1, 8],
+                color,
+                thickness: 3.0,
+            })
+            .overlay(Overlay::NextMoves {
+                algorithm: Algorithm::from_str("DLU").unwrap(),
+                count: 2,
+                color,
+            })
+            .overlay(Overlay::Dim {
+                pieces: vec![1, 2],
+                opacity: 0.5,
+            })
+            .build();
+
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="highlight""#).count(), 2);
+        assert_eq!(doc.matches(r#"class="arrow""#).count(), 2);
+        assert_eq!(doc.matches(r#"opacity="0.5""#).count(), 2);
+
+        // Moves with amount 0 have no arrow
+        let mut algorithm = Algorithm::from_str("D").unwrap();
+        algorithm.push(Move::new(Direction::Left, 0));
+        algorithm.push(Move::from_str("L").unwrap());
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .overlay(Overlay::NextMoves {
+                algorithm,
+                count: 3,
+                color,
+            })
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="arrow""#).count(), 2);
+        assert!(!doc.contains("NaN"));
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .overlay(Overlay::NextMoves {
+                algorithm: Algorithm::from_str("RU").unwrap(),
+                count: 2,
+                color,
+            })
+            .build();
+        assert_eq!(
+            renderer.render(&puzzle).map(|doc| doc.to_string()),
+            Err(RendererError::IllegalMove(Move::from_str("U").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_render_obstacle_puzzle() {
+        let puzzle = Puzzle::new(Size::new(3, 3).unwrap());
+        let obstacle_puzzle = ObstaclePuzzle::new(puzzle.clone(), &[(1, 2)]).unwrap();
+
+        let doc = renderer()
+            .render_obstacle_puzzle(&obstacle_puzzle)
+            .unwrap()
+            .to_string();
+        assert_eq!(doc.matches(r#"class="obstacle""#).count(), 1);
+        assert_eq!(doc.matches(r#"class="piece""#).count(), 7);
+        assert!(doc.contains("rect.obstacle { fill: #404040ff; }"));
+
+        // The obstacle style is only added when drawing an obstacle puzzle
+        let doc = renderer().render(&puzzle).unwrap().to_string();
+        assert!(!doc.contains("rect.obstacle"));
+    }
+
+    #[test]
+    fn test_next_moves_obstacles() {
+        let puzzle = ObstaclePuzzle::new(Puzzle::new(Size::new(3, 3).unwrap()), &[(1, 2)]).unwrap();
+        let next_moves = |algorithm: &str| {
+            RendererBuilder::with_dyn_scheme(Box::new(Black))
+                .overlay(Overlay::NextMoves {
+                    algorithm: Algorithm::from_str(algorithm).unwrap(),
+                    count: 2,
+                    color: Rgba::new(0.0, 0.0, 1.0, 1.0),
+                })
+                .build()
+                .render_obstacle_puzzle(&puzzle)
+                .map(|doc| doc.to_string().matches(r#"class="arrow""#).count())
+        };
+
+        assert_eq!(next_moves("DR"), Ok(2));
+
+        // The gap can not move into the obstacle, even though it is within the board
+        assert_eq!(
+            next_moves("R"),
+            Err(RendererError::IllegalMove(Move::from_str("R").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_layers() {
+        let scheme = BalancedSplitScheme::new(
+            Scheme::new(Rows, Rainbow::default()),
+            Rainbow::default(),
+            (4, 4),
+            Splitting::Quarters,
+        );
+        let puzzle = Puzzle::new(Size::new(8, 8).unwrap());
+
+        // Nested rectangles, with one layer hidden, using a boxed scheme
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .layers(
+                Layers::new(Box::new(scheme.clone()) as Box<dyn MultiLayerColorScheme>)
+                    .layer_style(1, LayerStyle::Hidden),
+            )
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="layer""#).count(), 63 * 2);
+        assert!(!doc.contains("layer-boundaries"));
+
+        // Stripes, with the boundaries of the 4 quarters and 16 smaller grids
+        let renderer = RendererBuilder::<Black, Black, Black, Black, _>::with_scheme(Black)
+            .layers(
+                Layers::new(scheme)
+                    .stripes()
+                    .boundaries(Rgba::new(1.0, 0.0, 0.0, 1.0), 4.0),
+            )
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"class="layer""#).count(), 63 * 3);
+        assert!(doc.contains(r#"height="25""#));
+        assert_eq!(doc.matches(r#"stroke-width="4""#).count(), 4);
+        assert_eq!(doc.matches(r#"stroke-width="2""#).count(), 16);
+    }
+
+    #[test]
+    fn test_grid_lines() {
+        let puzzle = Puzzle::new(Size::new(4, 4).unwrap());
+        let black = Rgba::new(0.0, 0.0, 0.0, 1.0);
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .grid_lines(
+                GridLines::new()
+                    .level(
+                        Box::new(RowGrids) as Box<dyn Grids>,
+                        StrokeStyle::new(black)
+                            .thickness(1.0)
+                            .dashes(vec![2.0, 2.0]),
+                    )
+                    .level(
+                        Box::new(Grid::new(vec![2], vec![2])),
+                        StrokeStyle::new(black).thickness(4.0),
+                    ),
+            )
+            .build();
+
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches(r#"stroke-dasharray="2 2""#).count(), 16);
+        assert_eq!(doc.matches(r#"stroke-width="4""#).count(), 4);
+        assert!(doc.contains(
+            r##"height="150" stroke="#000000ff" stroke-width="4" width="150" x="150" y="150""##
+        ));
+    }
+
+    #[test]
+    fn test_text_content() {
+        let size = Size::new(4, 4).unwrap();
+
+        assert_eq!(
+            TextContent::Number.text(12, size, (3, 2)),
+            Some("12".to_string())
+        );
+        assert_eq!(TextContent::Blank.text(12, size, (3, 2)), None);
+        assert_eq!(
+            [1, 26, 27, 52, 703].map(|piece| TextContent::Letters.text(piece, size, (0, 0))),
+            ["A", "Z", "AA", "AZ", "AAA"].map(|s| Some(s.to_string()))
+        );
+        assert_eq!(
+            TextContent::Label(Arc::new(Rows)).text(12, size, (3, 2)),
+            Some("2".to_string())
+        );
+        assert_eq!(
+            TextContent::SolvedPosition.text(12, size, (3, 2)),
+            Some("3,2".to_string())
+        );
+        assert_eq!(
+            TextContent::Custom(Arc::new(|piece, (x, _)| format!("{piece}:{x}"))).text(
+                12,
+                size,
+                (3, 2)
+            ),
+            Some("12:3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_text_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let text = Text::with_scheme(Black)
+            .content(TextContent::Custom(Arc::new(|piece, _| piece.to_string())));
+        assert_send_sync(&text);
+
+        let text = std::thread::spawn(move || text.content(TextContent::Label(Arc::new(Rows))))
+            .join()
+            .unwrap();
+        assert_send_sync(&text);
+    }
+
+    #[test]
+    fn test_render_text_content() {
+        let puzzle = Puzzle::from_str("2 1/3 0").unwrap();
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .text(
+                Text::with_scheme(Box::new(Black) as Box<dyn ColorScheme>)
+                    .content(TextContent::Letters),
+            )
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        let text = doc
+            .split("<text")
+            .skip(1)
+            .filter_map(|t| t.split(['>', '<']).nth(1))
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        assert_eq!(text, vec!["B", "A", "C"]);
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .text(
+                Text::with_scheme(Box::new(Black) as Box<dyn ColorScheme>)
+                    .content(TextContent::Blank),
+            )
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+        assert!(!doc.contains("<text"));
+    }
+
+    #[test]
+    fn test_image() {
+        let puzzle = Puzzle::from_str("1 2/0 3").unwrap();
+
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .borders(Borders::with_scheme(Box::new(Black) as Box<dyn ColorScheme>).thickness(2.0))
+            .tile_rounding(5.0)
+            .image(Image::Base64 {
+                data: "AAAA",
+                format: "png",
+            })
+            .build();
+        let doc = renderer.render(&puzzle).unwrap().to_string();
+
+        assert_eq!(doc.matches("<clipPath").count(), 1);
+        assert!(doc.contains(r#"id="tile-clip-75-2-5""#));
+        assert_eq!(
+            doc.matches(r#"clip-path="url(#tile-clip-75-2-5)""#).count(),
+            3
+        );
+        assert!(doc.contains(r#"height="73" rx="4" ry="4" width="73" x="1" y="1""#));
+        assert_eq!(
+            doc.matches(r#"href="data:image/png;base64,AAAA""#).count(),
+            3
+        );
 
-        Ok(doc)
+        // Piece 3 is at position (1, 1), and its solved position is (0, 1)
+        assert!(doc.contains(r#"transform="translate(78,78)""#));
+        assert!(doc.contains(r#"x="0" y="-77""#));
     }
 }
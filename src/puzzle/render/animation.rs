@@ -0,0 +1,162 @@
+//! Defines the [`AnimationTiming`] struct, used to control the animations created by
+//! [`Renderer::render_animation`].
+//!
+//! [`Renderer::render_animation`]: ../struct.Renderer.html#method.render_animation
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Easing function used when a piece slides from one position to the next.
+///
+/// The default value is [`Easing::EaseInOut`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Easing {
+    /// Pieces move at a constant speed.
+    Linear,
+    /// Pieces start slowly and speed up.
+    EaseIn,
+    /// Pieces start quickly and slow down.
+    EaseOut,
+    /// Pieces start slowly, speed up, and slow down again.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// The control points of the cubic Bézier curve describing the easing function, in the format
+    /// used by the SVG `keySplines` attribute.
+    #[must_use]
+    pub fn key_spline(&self) -> &'static str {
+        match self {
+            Self::Linear => "0 0 1 1",
+            Self::EaseIn => "0.42 0 1 1",
+            Self::EaseOut => "0 0 0.58 1",
+            Self::EaseInOut => "0.42 0 0.58 1",
+        }
+    }
+
+    /// Evaluates the easing function at time `t`, where `t` is between 0 and 1. The result is the
+    /// fraction of the distance that a piece has moved at time `t`.
+    #[must_use]
+    pub fn apply(&self, t: f32) -> f32 {
+        let (x1, y1, x2, y2) = match self {
+            Self::Linear => return t.clamp(0.0, 1.0),
+            Self::EaseIn => (0.42, 0.0, 1.0, 1.0),
+            Self::EaseOut => (0.0, 0.0, 0.58, 1.0),
+            Self::EaseInOut => (0.42, 0.0, 0.58, 1.0),
+        };
+
+        let bezier = |p1: f32, p2: f32, s: f32| {
+            3.0 * (1.0 - s) * (1.0 - s) * s * p1 + 3.0 * (1.0 - s) * s * s * p2 + s * s * s
+        };
+
+        // The x coordinate of the curve is increasing, so we can find the parameter `s` where it
+        // is equal to `t` by bisection
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..32 {
+            let mid = f32::midpoint(lo, hi);
+            if bezier(x1, x2, mid) < t {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        bezier(y1, y2, f32::midpoint(lo, hi))
+    }
+}
+
+/// Timing options for an animation of an algorithm being applied to a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnimationTiming {
+    pub(super) move_duration: f32,
+    pub(super) end_delay: f32,
+    pub(super) easing: Easing,
+    pub(super) looping: bool,
+}
+
+impl Default for AnimationTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationTiming {
+    /// Create a new [`AnimationTiming`] instance. The default is 0.25 seconds per move with
+    /// [`Easing::EaseInOut`], no delay at the end, and no looping.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            move_duration: 0.25,
+            end_delay: 0.0,
+            easing: Easing::EaseInOut,
+            looping: false,
+        }
+    }
+
+    /// Set the duration of each move in seconds.
+    #[must_use]
+    pub fn move_duration(mut self, duration: f32) -> Self {
+        self.move_duration = duration.max(0.0);
+        self
+    }
+
+    /// Set the time in seconds that the final state is shown for before the animation ends (or
+    /// restarts, if the animation is looping).
+    #[must_use]
+    pub fn end_delay(mut self, delay: f32) -> Self {
+        self.end_delay = delay.max(0.0);
+        self
+    }
+
+    /// Set the easing function.
+    #[must_use]
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set whether the animation restarts from the beginning when it ends.
+    #[must_use]
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// The total duration in seconds of an animation of `num_moves` moves.
+    #[must_use]
+    pub fn total_duration(&self, num_moves: usize) -> f32 {
+        self.move_duration * num_moves as f32 + self.end_delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing_apply() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert!(easing.apply(0.0).abs() < 1e-4);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4);
+        }
+
+        assert!((Easing::Linear.apply(0.3) - 0.3).abs() < 1e-4);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-4);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn test_total_duration() {
+        let timing = AnimationTiming::new().move_duration(0.5).end_delay(1.0);
+        assert!((timing.total_duration(4) - 3.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,392 @@
+//! Defines the [`RendererConfig`] struct, a data-driven description of a [`Renderer`] that can be
+//! saved and shared (e.g. as JSON, with the `serde` feature).
+//!
+//! [`Renderer`]: ../struct.Renderer.html
+
+use palette::rgb::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::puzzle::{
+    color_scheme::{ColorScheme, Scheme},
+    coloring::{AddLightness, AlternatingBrightness, ColorList, Coloring, Monochrome, Rainbow},
+    label::label::{
+        Checkerboard, ConcentricRectangles, Diagonals, Fringe, FringeGrids, Label, LastTwoRows,
+        RowGrids, Rows, Spiral, SpiralGrids, SplitFringe, SplitLastTwoRows, SplitSquareFringe,
+        SquareFringe, Trivial,
+    },
+    render::{Borders, Font, Renderer, RendererBuilder, Text},
+};
+
+/// A [`Label`] that can be used in a [`RendererConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LabelConfig {
+    /// See [`Trivial`].
+    Trivial,
+    /// See [`RowGrids`].
+    RowGrids,
+    /// See [`Rows`].
+    Rows,
+    /// See [`Fringe`].
+    Fringe,
+    /// See [`FringeGrids`].
+    FringeGrids,
+    /// See [`SquareFringe`].
+    SquareFringe,
+    /// See [`SplitFringe`].
+    SplitFringe,
+    /// See [`SplitSquareFringe`].
+    SplitSquareFringe,
+    /// See [`Diagonals`].
+    Diagonals,
+    /// See [`LastTwoRows`].
+    LastTwoRows,
+    /// See [`SplitLastTwoRows`].
+    SplitLastTwoRows,
+    /// See [`ConcentricRectangles`].
+    ConcentricRectangles,
+    /// See [`Spiral`].
+    Spiral,
+    /// See [`SpiralGrids`].
+    SpiralGrids,
+    /// See [`Checkerboard`].
+    Checkerboard,
+}
+
+impl LabelConfig {
+    /// The [`Label`] described by `self`.
+    #[must_use]
+    pub fn label(&self) -> Box<dyn Label> {
+        match self {
+            Self::Trivial => Box::new(Trivial),
+            Self::RowGrids => Box::new(RowGrids),
+            Self::Rows => Box::new(Rows),
+            Self::Fringe => Box::new(Fringe),
+            Self::FringeGrids => Box::new(FringeGrids),
+            Self::SquareFringe => Box::new(SquareFringe),
+            Self::SplitFringe => Box::new(SplitFringe),
+            Self::SplitSquareFringe => Box::new(SplitSquareFringe),
+            Self::Diagonals => Box::new(Diagonals),
+            Self::LastTwoRows => Box::new(LastTwoRows),
+            Self::SplitLastTwoRows => Box::new(SplitLastTwoRows),
+            Self::ConcentricRectangles => Box::new(ConcentricRectangles),
+            Self::Spiral => Box::new(Spiral),
+            Self::SpiralGrids => Box::new(SpiralGrids),
+            Self::Checkerboard => Box::new(Checkerboard),
+        }
+    }
+}
+
+/// A [`Coloring`] that can be used in a [`RendererConfig`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColoringConfig {
+    /// See [`Monochrome`].
+    Monochrome(Monochrome),
+    /// See [`ColorList`].
+    ColorList(ColorList),
+    /// See [`Rainbow`].
+    Rainbow(Rainbow),
+    /// See [`AlternatingBrightness`].
+    AlternatingBrightness(Box<Self>),
+    /// See [`AddLightness`].
+    AddLightness {
+        /// The coloring to modify.
+        coloring: Box<Self>,
+        /// The amount to add to the lightness value.
+        lightness: f32,
+    },
+}
+
+impl ColoringConfig {
+    /// The [`Coloring`] described by `self`.
+    #[must_use]
+    pub fn coloring(&self) -> Box<dyn Coloring> {
+        match self {
+            Self::Monochrome(c) => Box::new(c.clone()),
+            Self::ColorList(c) => Box::new(c.clone()),
+            Self::Rainbow(c) => Box::new(c.clone()),
+            Self::AlternatingBrightness(c) => Box::new(AlternatingBrightness(c.coloring())),
+            Self::AddLightness {
+                coloring,
+                lightness,
+            } => Box::new(AddLightness::new(coloring.coloring(), *lightness)),
+        }
+    }
+}
+
+/// A [`ColorScheme`] made from a [`Label`] and a [`Coloring`] (see [`Scheme`]), that can be used
+/// in a [`RendererConfig`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SchemeConfig {
+    /// The label.
+    pub label: LabelConfig,
+    /// The coloring.
+    pub coloring: ColoringConfig,
+}
+
+impl SchemeConfig {
+    /// Creates a new [`SchemeConfig`].
+    #[must_use]
+    pub fn new(label: LabelConfig, coloring: ColoringConfig) -> Self {
+        Self { label, coloring }
+    }
+
+    /// Creates a new [`SchemeConfig`] that colors every piece with `color`.
+    #[must_use]
+    pub fn monochrome(color: Rgba) -> Self {
+        Self::new(
+            LabelConfig::Trivial,
+            ColoringConfig::Monochrome(Monochrome::new(color)),
+        )
+    }
+
+    /// The [`ColorScheme`] described by `self`.
+    #[must_use]
+    pub fn scheme(&self) -> Box<dyn ColorScheme> {
+        Box::new(Scheme::new(self.label.label(), self.coloring.coloring()))
+    }
+}
+
+/// A [`Font`] that can be used in a [`RendererConfig`]. Unlike [`Font`], the strings are owned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FontConfig {
+    /// See [`Font::Family`].
+    Family(String),
+    /// See [`Font::Url`].
+    Url {
+        /// Path to the font.
+        path: String,
+        /// Format of the font file.
+        format: String,
+    },
+    /// See [`Font::Base64`].
+    Base64 {
+        /// Base 64 font data.
+        data: String,
+        /// Format of the font data.
+        format: String,
+    },
+}
+
+impl FontConfig {
+    /// The [`Font`] described by `self`.
+    #[must_use]
+    pub fn font(&self) -> Font<'_> {
+        match self {
+            Self::Family(family) => Font::Family(family),
+            Self::Url { path, format } => Font::Url { path, format },
+            Self::Base64 { data, format } => Font::Base64 { data, format },
+        }
+    }
+}
+
+/// Describes the [`Borders`] of a [`RendererConfig`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BordersConfig {
+    /// The border color scheme.
+    pub scheme: SchemeConfig,
+    /// The border thickness.
+    pub thickness: f32,
+}
+
+/// Describes the [`Text`] of a [`RendererConfig`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextConfig {
+    /// The text color scheme.
+    pub scheme: SchemeConfig,
+    /// The font.
+    pub font: FontConfig,
+    /// The font size.
+    pub font_size: f32,
+    /// The position of the text within each tile, see [`Text::position`].
+    pub position: (f32, f32),
+}
+
+impl Default for TextConfig {
+    fn default() -> Self {
+        Self {
+            scheme: SchemeConfig::monochrome(Rgba::new(0.0, 0.0, 0.0, 1.0)),
+            font: FontConfig::Family("sans-serif".to_string()),
+            font_size: 30.0,
+            position: (0.5, 0.5),
+        }
+    }
+}
+
+/// A description of a [`Renderer`] using only plain data, so that it can be (de)serialized with
+/// the `serde` feature. Use [`RendererConfig::build`] to create the [`Renderer`].
+///
+/// When deserializing, missing fields are taken from [`RendererConfig::default`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct RendererConfig {
+    /// The main color scheme.
+    pub scheme: SchemeConfig,
+    /// The borders, if any.
+    pub borders: Option<BordersConfig>,
+    /// The text, if any.
+    pub text: Option<TextConfig>,
+    /// The tile size in pixels.
+    pub tile_size: f32,
+    /// The rounding radius of the tile corners in pixels.
+    pub tile_rounding: f32,
+    /// The gap between tiles in pixels.
+    pub tile_gap: f32,
+    /// The padding around the edge of the puzzle in pixels.
+    pub padding: f32,
+    /// The background color.
+    pub background_color: Rgba,
+}
+
+impl Default for RendererConfig {
+    /// Pieces colored by [`Fringe`] with a [`Rainbow`] coloring, with black text and borders.
+    fn default() -> Self {
+        Self {
+            scheme: SchemeConfig::new(
+                LabelConfig::Fringe,
+                ColoringConfig::Rainbow(Rainbow::default()),
+            ),
+            borders: Some(BordersConfig {
+                scheme: SchemeConfig::monochrome(Rgba::new(0.0, 0.0, 0.0, 1.0)),
+                thickness: 1.0,
+            }),
+            text: Some(TextConfig::default()),
+            tile_size: 75.0,
+            tile_rounding: 0.0,
+            tile_gap: 0.0,
+            padding: 0.0,
+            background_color: Rgba::new(1.0, 1.0, 1.0, 0.0),
+        }
+    }
+}
+
+impl RendererConfig {
+    /// Dark gray pieces on a dark background, with white text and [`SplitFringe`] [`Rainbow`]
+    /// borders.
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            scheme: SchemeConfig::monochrome(Rgba::new(0.15, 0.15, 0.15, 1.0)),
+            borders: Some(BordersConfig {
+                scheme: SchemeConfig::new(
+                    LabelConfig::SplitFringe,
+                    ColoringConfig::Rainbow(Rainbow::default()),
+                ),
+                thickness: 5.0,
+            }),
+            text: Some(TextConfig {
+                scheme: SchemeConfig::monochrome(Rgba::new(1.0, 1.0, 1.0, 1.0)),
+                font_size: 40.0,
+                ..TextConfig::default()
+            }),
+            tile_size: 75.0,
+            tile_rounding: 10.0,
+            tile_gap: 5.0,
+            padding: 10.0,
+            background_color: Rgba::new(0.05, 0.05, 0.05, 1.0),
+        }
+    }
+
+    /// Creates a [`RendererBuilder`] configured as described by `self`, which can be further
+    /// configured before building.
+    #[must_use]
+    pub fn builder(&self) -> RendererBuilder<'_> {
+        let mut builder = RendererBuilder::with_dyn_scheme(self.scheme.scheme())
+            .tile_size(self.tile_size)
+            .tile_rounding(self.tile_rounding)
+            .tile_gap(self.tile_gap)
+            .padding(self.padding)
+            .background_color(self.background_color);
+
+        if let Some(borders) = &self.borders {
+            builder = builder.borders(
+                Borders::with_scheme(borders.scheme.scheme()).thickness(borders.thickness),
+            );
+        }
+
+        if let Some(text) = &self.text {
+            builder = builder.text(
+                Text::with_scheme(text.scheme.scheme())
+                    .font(text.font.font())
+                    .font_size(text.font_size)
+                    .position(text.position),
+            );
+        }
+
+        builder
+    }
+
+    /// Builds the [`Renderer`] described by `self`.
+    #[must_use]
+    pub fn build(&self) -> Renderer<'_> {
+        self.builder().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::{puzzle::Puzzle, size::Size};
+
+    #[test]
+    fn test_build() {
+        let puzzle = Puzzle::new(Size::new(4, 4).unwrap());
+
+        let config = RendererConfig::default();
+        let doc = config.build().render(&puzzle).unwrap().to_string();
+        assert_eq!(doc.matches("<text").count(), 15);
+        assert!(doc.contains(r#"width="304""#));
+
+        let config = RendererConfig::dark();
+        let doc = config.build().render(&puzzle).unwrap().to_string();
+        assert!(doc.contains("svg { background-color: #0d0d0dff; }"));
+        assert!(doc.contains(r#"rx="10""#));
+        assert!(doc.contains("font-size: 40px"));
+        assert!(doc.contains(r##"fill="#262626ff""##));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let config = RendererConfig::dark();
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RendererConfig>(&json).unwrap(),
+            config
+        );
+
+        // Missing fields are taken from the default config
+        let config =
+            serde_json::from_str::<RendererConfig>(r#"{"tile_size": 50.0, "borders": null}"#)
+                .unwrap();
+        assert_eq!(
+            config,
+            RendererConfig {
+                tile_size: 50.0,
+                borders: None,
+                ..RendererConfig::default()
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<RendererConfig>("{}").unwrap(),
+            RendererConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_coloring() {
+        let config = ColoringConfig::AddLightness {
+            coloring: Box::new(ColoringConfig::Monochrome(Monochrome::new(Rgba::new(
+                0.0, 0.0, 0.0, 1.0,
+            )))),
+            lightness: 1.0,
+        };
+        assert_eq!(config.coloring().color(0, 1), Rgba::new(1.0, 1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,132 @@
+//! Defines the [`GridLines`] struct, used to draw the boundaries of the grids of one or more
+//! [`Grids`] with [`RendererBuilder::grid_lines`].
+//!
+//! [`RendererBuilder::grid_lines`]: ../struct.RendererBuilder.html#method.grid_lines
+
+use itertools::Itertools as _;
+use palette::rgb::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use svg::node::element::Rectangle;
+
+use crate::puzzle::grids::Grids;
+
+/// The style of a line drawn over the puzzle.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StrokeStyle {
+    color: Rgba,
+    thickness: f32,
+    dashes: Vec<f32>,
+}
+
+impl StrokeStyle {
+    /// Create a new [`StrokeStyle`]. The default is a solid line with thickness 2 pixels.
+    #[must_use]
+    pub fn new(color: Rgba) -> Self {
+        Self {
+            color,
+            thickness: 2.0,
+            dashes: Vec::new(),
+        }
+    }
+
+    /// Set the color of the line.
+    #[must_use]
+    pub fn color(mut self, color: Rgba) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the thickness of the line in pixels.
+    #[must_use]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness.max(0.0);
+        self
+    }
+
+    /// Set the dash pattern of the line, as a list of alternating dash and gap lengths in pixels,
+    /// in the format of the SVG `stroke-dasharray` attribute. An empty list draws a solid line.
+    #[must_use]
+    pub fn dashes(mut self, dashes: Vec<f32>) -> Self {
+        self.dashes = dashes;
+        self
+    }
+
+    /// Sets the stroke attributes of `rect`.
+    pub(super) fn apply(&self, rect: Rectangle) -> Rectangle {
+        let stroke = {
+            let color: Rgba<_, u8> = self.color.into_format();
+            format!("#{color:x}")
+        };
+
+        let rect = rect
+            .set("fill", "none")
+            .set("stroke", stroke)
+            .set("stroke-width", self.thickness);
+
+        if self.dashes.is_empty() {
+            rect
+        } else {
+            rect.set("stroke-dasharray", self.dashes.iter().join(" "))
+        }
+    }
+}
+
+/// Struct containing the information needed to draw the boundaries of grids over the puzzle.
+///
+/// Each level is a [`Grids`] together with the [`StrokeStyle`] used to draw the boundaries of its
+/// grids. Levels are drawn in the order they were added, so for nested grids (e.g. the blocks of a
+/// block-building method, and the smaller blocks inside them), the largest grids should usually be
+/// added last so that their boundaries are drawn on top.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridLines<G: Grids> {
+    levels: Vec<(G, StrokeStyle)>,
+}
+
+impl<G: Grids> Default for GridLines<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Grids> GridLines<G> {
+    /// Create a new [`GridLines`] instance with no levels.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { levels: Vec::new() }
+    }
+
+    /// Add a nesting level, drawing the boundaries of the grids of `grids` with style `style`.
+    #[must_use]
+    pub fn level(mut self, grids: G, style: StrokeStyle) -> Self {
+        self.levels.push((grids, style));
+        self
+    }
+
+    /// The nesting levels, in the order they are drawn.
+    #[must_use]
+    pub fn levels(&self) -> &[(G, StrokeStyle)] {
+        &self.levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stroke_style() {
+        let style = StrokeStyle::new(Rgba::new(1.0, 0.0, 0.0, 1.0)).thickness(3.0);
+        let rect = style.apply(Rectangle::new()).to_string();
+        assert!(rect.contains(r##"stroke="#ff0000ff""##));
+        assert!(rect.contains(r#"stroke-width="3""#));
+        assert!(!rect.contains("stroke-dasharray"));
+
+        let rect = style
+            .dashes(vec![4.0, 2.5])
+            .apply(Rectangle::new())
+            .to_string();
+        assert!(rect.contains(r#"stroke-dasharray="4 2.5""#));
+    }
+}
@@ -0,0 +1,206 @@
+//! Defines the [`Layers`] struct, used to draw each layer of a [`MultiLayerColorScheme`] with
+//! [`RendererBuilder::layers`].
+//!
+//! [`RendererBuilder::layers`]: ../struct.RendererBuilder.html#method.layers
+
+use std::collections::BTreeMap;
+
+use palette::rgb::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::puzzle::{
+    color_scheme::multi_layer::{Layer, MultiLayerColorScheme},
+    grids::Grids,
+    label::rect_partition::Rect,
+    size::Size,
+};
+
+/// Ways that a single layer of a [`MultiLayerColorScheme`] can be drawn on each piece.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LayerStyle {
+    /// Draw the layer as a rectangle inset from the edge of the piece by the given fraction of
+    /// the tile size.
+    Inset(f32),
+    /// Draw the layer as a horizontal stripe. The stripes of all layers with this style are
+    /// stacked from top to bottom and share the height of the piece equally. Stripes are not
+    /// rounded, even if the tiles are.
+    Stripe,
+    /// Don't draw the layer.
+    Hidden,
+}
+
+/// The style used for layers that do not have their own [`LayerStyle`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DefaultStyle {
+    Nested(f32),
+    Stripes,
+}
+
+/// Information needed to draw the boundaries of the grids of each layer.
+#[derive(Clone, Copy, Debug)]
+struct Boundaries<M> {
+    color: Rgba,
+    thickness: f32,
+    grids: fn(&M, Size, u32, (u64, u64)) -> Option<Rect>,
+}
+
+impl<M> PartialEq for Boundaries<M> {
+    fn eq(&self, other: &Self) -> bool {
+        // `grids` is always the same function for a given `M`, so there is no need to compare it
+        self.color == other.color && self.thickness == other.thickness
+    }
+}
+
+/// Struct containing the information needed to draw the layers of a [`MultiLayerColorScheme`] on
+/// the pieces of the puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layers<M: MultiLayerColorScheme> {
+    scheme: M,
+    default_style: DefaultStyle,
+    styles: BTreeMap<u32, LayerStyle>,
+    boundaries: Option<Boundaries<M>>,
+}
+
+impl<M: MultiLayerColorScheme> Layers<M> {
+    /// Create a new [`Layers`] instance. The default is to draw the layers as nested rectangles,
+    /// each one inset by 10% of the tile size more than the previous one, with no boundaries.
+    #[must_use]
+    pub fn new(scheme: M) -> Self {
+        Self {
+            scheme,
+            default_style: DefaultStyle::Nested(0.1),
+            styles: BTreeMap::new(),
+            boundaries: None,
+        }
+    }
+
+    /// Draw the layers as nested rectangles, with layer `n` inset by `n * step` times the tile
+    /// size.
+    #[must_use]
+    pub fn nested(mut self, step: f32) -> Self {
+        self.default_style = DefaultStyle::Nested(step.max(0.0));
+        self
+    }
+
+    /// Draw the layers as stacked horizontal stripes, with layer 0 at the top.
+    #[must_use]
+    pub fn stripes(mut self) -> Self {
+        self.default_style = DefaultStyle::Stripes;
+        self
+    }
+
+    /// Set the style of a single layer, overriding the style set by [`Layers::nested`] or
+    /// [`Layers::stripes`].
+    #[must_use]
+    pub fn layer_style(mut self, layer: u32, style: LayerStyle) -> Self {
+        self.styles.insert(layer, style);
+        self
+    }
+
+    /// Draw the boundaries of the grids of each layer (see [`Grids`]) over the puzzle, using the
+    /// given color. The boundaries of layer `n` are drawn with thickness `thickness / (n + 1)`,
+    /// so that larger grids have thicker boundaries.
+    #[must_use]
+    pub fn boundaries(mut self, color: Rgba, thickness: f32) -> Self
+    where
+        for<'b> Layer<&'b M>: Grids,
+    {
+        self.boundaries = Some(Boundaries {
+            color,
+            thickness: thickness.max(0.0),
+            grids: |scheme, size, layer, pos| {
+                scheme
+                    .layer(size, layer)
+                    .map(|layer| layer.grid_containing_pos(size, pos))
+            },
+        });
+        self
+    }
+
+    /// The multi-layer color scheme.
+    #[must_use]
+    pub fn scheme(&self) -> &M {
+        &self.scheme
+    }
+
+    /// The style used to draw layer `layer`.
+    #[must_use]
+    pub fn style(&self, layer: u32) -> LayerStyle {
+        self.styles
+            .get(&layer)
+            .copied()
+            .unwrap_or(match self.default_style {
+                DefaultStyle::Nested(step) => LayerStyle::Inset(step * layer as f32),
+                DefaultStyle::Stripes => LayerStyle::Stripe,
+            })
+    }
+
+    /// The color and thickness of the boundaries of layer `layer`, if boundaries are drawn.
+    pub(super) fn boundary_style(&self, layer: u32) -> Option<(Rgba, f32)> {
+        self.boundaries
+            .as_ref()
+            .map(|b| (b.color, b.thickness / (layer + 1) as f32))
+    }
+
+    /// The grid of layer `layer` that contains `pos`, if boundaries are drawn.
+    pub(super) fn grid_containing_pos(
+        &self,
+        size: Size,
+        layer: u32,
+        pos: (u64, u64),
+    ) -> Option<Rect> {
+        let boundaries = self.boundaries.as_ref()?;
+        (boundaries.grids)(&self.scheme, size, layer, pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::{
+        color_scheme::{
+            balanced_split::{BalancedSplitScheme, Splitting},
+            Scheme,
+        },
+        coloring::Monochrome,
+        label::label::Rows,
+    };
+
+    fn scheme() -> BalancedSplitScheme<Scheme<Rows, Monochrome>, Monochrome> {
+        let black = Monochrome::new(Rgba::new(0.0, 0.0, 0.0, 1.0));
+        BalancedSplitScheme::new(
+            Scheme::new(Rows, black.clone()),
+            black,
+            (4, 4),
+            Splitting::Quarters,
+        )
+    }
+
+    #[test]
+    fn test_style() {
+        let layers = Layers::new(scheme()).nested(0.2);
+        assert_eq!(layers.style(0), LayerStyle::Inset(0.0));
+        assert_eq!(layers.style(2), LayerStyle::Inset(0.4));
+
+        let layers = layers.stripes().layer_style(1, LayerStyle::Hidden);
+        assert_eq!(layers.style(0), LayerStyle::Stripe);
+        assert_eq!(layers.style(1), LayerStyle::Hidden);
+    }
+
+    #[test]
+    fn test_grid_containing_pos() {
+        let size = Size::new(8, 8).unwrap();
+
+        let layers = Layers::new(scheme());
+        assert_eq!(layers.grid_containing_pos(size, 0, (5, 2)), None);
+
+        let layers = layers.boundaries(Rgba::new(0.0, 0.0, 0.0, 1.0), 4.0);
+        assert_eq!(
+            layers.grid_containing_pos(size, 0, (5, 2)),
+            Some(Rect::new((4, 0), (8, 4)).unwrap())
+        );
+        assert_eq!(layers.boundary_style(1).map(|(_, t)| t), Some(2.0));
+    }
+}
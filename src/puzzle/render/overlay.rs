@@ -0,0 +1,139 @@
+//! Defines the [`Overlay`] type, used to draw extra information on top of the pieces of a puzzle
+//! with [`RendererBuilder::overlay`].
+//!
+//! [`RendererBuilder::overlay`]: ../struct.RendererBuilder.html#method.overlay
+
+use num_traits::ToPrimitive as _;
+use palette::rgb::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    algorithm::algorithm::Algorithm,
+    puzzle::{
+        render::config::LabelConfig,
+        sliding_puzzle::SlidingPuzzle,
+        solved_state::{Partial, SolvedState as _},
+    },
+};
+
+/// Something to draw on top of the pieces of a puzzle, or a change to how the pieces are drawn.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Overlay {
+    /// Draw an outline around each of the given pieces.
+    Highlight {
+        /// The pieces to highlight.
+        pieces: Vec<u64>,
+        /// Color of the outline.
+        color: Rgba,
+        /// Thickness of the outline in pixels.
+        thickness: f32,
+    },
+    /// Draw an arrow for each of the first `count` moves of `algorithm`, starting from the current
+    /// state of the puzzle.
+    NextMoves {
+        /// The algorithm to show the moves of.
+        algorithm: Algorithm,
+        /// The number of moves to show.
+        count: usize,
+        /// Color of the arrows.
+        color: Rgba,
+    },
+    /// Draw the given pieces with reduced opacity.
+    Dim {
+        /// The pieces to dim.
+        pieces: Vec<u64>,
+        /// Opacity of the dimmed pieces, between 0 and 1.
+        opacity: f32,
+    },
+    /// Draw the pieces that are already solved with reduced opacity. Whether a piece is solved is
+    /// checked each time the puzzle is drawn, using [`SolvedState::is_solved_at`] for `label`, or
+    /// for [`Partial::new(label, labels)`] if `labels` is not `None`.
+    ///
+    /// [`Partial::new(label, labels)`]: ../../solved_state/struct.Partial.html#method.new
+    DimSolved {
+        /// The label that defines the solved state.
+        label: LabelConfig,
+        /// The labels of the positions that need to be solved, or `None` if all positions do.
+        labels: Option<Vec<u64>>,
+        /// Opacity of the dimmed pieces, between 0 and 1.
+        opacity: f32,
+    },
+}
+
+impl Overlay {
+    /// Creates an [`Overlay::DimSolved`] that dims the pieces that are solved in the solved state
+    /// defined by `label`.
+    #[must_use]
+    pub fn dim_solved(label: LabelConfig, opacity: f32) -> Self {
+        Self::DimSolved {
+            label,
+            labels: None,
+            opacity,
+        }
+    }
+
+    /// The opacity of the piece at position `pos` of `puzzle`, if this is an [`Overlay::Dim`] or
+    /// [`Overlay::DimSolved`] that dims it. The gap is never dimmed.
+    pub(super) fn dim_opacity<Puzzle>(&self, puzzle: &Puzzle, pos: (u64, u64)) -> Option<f32>
+    where
+        Puzzle: SlidingPuzzle,
+    {
+        let piece = puzzle
+            .piece_at_xy(pos)
+            .to_u64()
+            .filter(|&piece| piece != 0)?;
+
+        match self {
+            Self::Dim { pieces, opacity } => pieces.contains(&piece).then_some(*opacity),
+            Self::DimSolved {
+                label,
+                labels,
+                opacity,
+            } => {
+                let is_solved = labels.as_ref().map_or_else(
+                    || label.label().is_solved_at(puzzle, pos),
+                    |labels| Partial::new(label.label(), labels.clone()).is_solved_at(puzzle, pos),
+                );
+                is_solved.then_some(*opacity)
+            }
+            Self::Highlight { .. } | Self::NextMoves { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::puzzle::puzzle::Puzzle;
+
+    #[test]
+    fn test_dim_solved() {
+        let mut p = Puzzle::from_str("1 2 3/5 4 6/7 0 8").unwrap();
+        let overlay = Overlay::dim_solved(LabelConfig::RowGrids, 0.5);
+        let dimmed = |p: &Puzzle| {
+            (0..3)
+                .flat_map(|y| (0..3).map(move |x| (x, y)))
+                .filter(|&pos| overlay.dim_opacity(p, pos) == Some(0.5))
+                .map(|pos| p.piece_at_xy(pos))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(dimmed(&p), vec![1, 2, 3, 6, 7]);
+
+        // Solved pieces are found when drawing, not when the overlay is created
+        p.apply_alg(&Algorithm::from_str("L").unwrap());
+        assert_eq!(dimmed(&p), vec![1, 2, 3, 6, 7, 8]);
+
+        let overlay = Overlay::DimSolved {
+            label: LabelConfig::RowGrids,
+            labels: Some(vec![0, 1, 2]),
+            opacity: 0.5,
+        };
+        assert_eq!(overlay.dim_opacity(&p, (0, 0)), Some(0.5));
+        assert_eq!(overlay.dim_opacity(&p, (0, 2)), None);
+    }
+}
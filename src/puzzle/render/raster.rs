@@ -0,0 +1,191 @@
+//! Defines the [`RasterError`] type, and helper functions used by [`Renderer`] to rasterize SVG
+//! images into PNG and GIF images without any external tools.
+//!
+//! This module is only available with the `raster` feature.
+//!
+//! [`Renderer`]: ../struct.Renderer.html
+
+use std::sync::OnceLock;
+
+use gif::{Encoder, Frame, Repeat};
+use palette::rgb::{Rgb, Rgba};
+use resvg::{
+    tiny_skia::{Color, Pixmap, Transform},
+    usvg::{fontdb::Database, Options, Tree},
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use svg::Document;
+use thiserror::Error;
+
+use crate::puzzle::render::RendererError;
+
+/// Error type for rasterizing images with [`Renderer`].
+///
+/// [`Renderer`]: ../struct.Renderer.html
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RasterError {
+    /// Returned when the SVG image could not be drawn.
+    #[error("Renderer: {0}")]
+    Renderer(#[from] RendererError),
+
+    /// Returned when the SVG image could not be parsed by the rasterizer.
+    #[error("InvalidSvg: {0}")]
+    InvalidSvg(String),
+
+    /// Returned when the image has zero width or height, or is too large to be rasterized.
+    #[error("InvalidImageSize: image size {width}x{height} can not be rasterized")]
+    InvalidImageSize {
+        /// Width of the image in pixels.
+        width: u32,
+        /// Height of the image in pixels.
+        height: u32,
+    },
+
+    /// Returned when encoding the image failed.
+    #[error("Encoding: {0}")]
+    Encoding(String),
+}
+
+/// Font database shared by all rasterized images. System fonts are only loaded once.
+fn fontdb() -> &'static std::sync::Arc<Database> {
+    static FONTDB: OnceLock<std::sync::Arc<Database>> = OnceLock::new();
+    FONTDB.get_or_init(|| {
+        let mut db = Database::new();
+        db.load_system_fonts();
+        std::sync::Arc::new(db)
+    })
+}
+
+/// Rasterizes an SVG document on top of a background of color `background`.
+pub(super) fn rasterize(doc: &Document, background: Rgba) -> Result<Pixmap, RasterError> {
+    let options = Options {
+        fontdb: fontdb().clone(),
+        ..Options::default()
+    };
+
+    let tree = Tree::from_str(&doc.to_string(), &options)
+        .map_err(|e| RasterError::InvalidSvg(e.to_string()))?;
+
+    let size = tree.size().to_int_size();
+    let (width, height) = (size.width(), size.height());
+    let mut pixmap =
+        Pixmap::new(width, height).ok_or(RasterError::InvalidImageSize { width, height })?;
+
+    // The background color is set using CSS on the root element, which is not drawn by resvg
+    let Rgba {
+        color: Rgb {
+            red, green, blue, ..
+        },
+        alpha,
+    } = background;
+    if let Some(color) = Color::from_rgba(red, green, blue, alpha) {
+        pixmap.fill(color);
+    }
+
+    resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+
+    Ok(pixmap)
+}
+
+/// Encodes a single image as PNG.
+pub(super) fn encode_png(pixmap: &Pixmap) -> Result<Vec<u8>, RasterError> {
+    pixmap
+        .encode_png()
+        .map_err(|e| RasterError::Encoding(e.to_string()))
+}
+
+/// Encodes a sequence of images of equal size as an animated GIF. Each frame is paired with its
+/// delay in hundredths of a second.
+pub(super) fn encode_gif(frames: &[(Pixmap, u16)], looping: bool) -> Result<Vec<u8>, RasterError> {
+    let Some((first, _)) = frames.first() else {
+        return Err(RasterError::InvalidImageSize {
+            width: 0,
+            height: 0,
+        });
+    };
+
+    let (width, height) = (first.width(), first.height());
+    let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(RasterError::InvalidImageSize { width, height });
+    };
+
+    let encoding_error = |e: gif::EncodingError| RasterError::Encoding(e.to_string());
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = Encoder::new(&mut bytes, w, h, &[]).map_err(encoding_error)?;
+        encoder
+            .set_repeat(if looping {
+                Repeat::Infinite
+            } else {
+                Repeat::Finite(0)
+            })
+            .map_err(encoding_error)?;
+
+        for (pixmap, delay) in frames {
+            // The pixmap uses premultiplied alpha, but GIF frames need straight alpha
+            let mut pixels = pixmap
+                .pixels()
+                .iter()
+                .flat_map(|p| {
+                    let c = p.demultiply();
+                    [c.red(), c.green(), c.blue(), c.alpha()]
+                })
+                .collect::<Vec<_>>();
+
+            let mut frame = Frame::from_rgba_speed(w, h, &mut pixels, 10);
+            frame.delay = *delay;
+            encoder.write_frame(&frame).map_err(encoding_error)?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::{
+        algorithm::algorithm::Algorithm,
+        puzzle::{
+            color_scheme::Black, puzzle::Puzzle, render::animation::AnimationTiming,
+            render::RendererBuilder, size::Size,
+        },
+    };
+
+    #[test]
+    fn test_rasterize() {
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black)).build();
+        let puzzle = Puzzle::new(Size::new(4, 3).unwrap());
+        let pixmap = rasterize(
+            &renderer.render(&puzzle).unwrap(),
+            renderer.background_color,
+        )
+        .unwrap();
+
+        assert_eq!((pixmap.width(), pixmap.height()), (300, 225));
+
+        // Pieces are black, and the gap is transparent
+        let piece = pixmap.pixel(37, 37).unwrap();
+        assert_eq!((piece.red(), piece.alpha()), (0, 255));
+        assert_eq!(pixmap.pixel(262, 187).unwrap().alpha(), 0);
+    }
+
+    #[test]
+    fn test_render_gif() {
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black)).build();
+        let puzzle = Puzzle::new(Size::new(3, 3).unwrap());
+        let alg = Algorithm::from_str("DR").unwrap();
+        let timing = AnimationTiming::new().move_duration(0.2);
+
+        let gif = renderer.render_gif(&puzzle, &alg, &timing, 10.0).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let png = renderer.render_png(&puzzle).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,315 @@
+//! Defines the [`SheetRenderer`] struct, for drawing many puzzles arranged in a grid, e.g. for a
+//! scramble sheet.
+
+use std::fmt::Display;
+
+use svg::{
+    node::{
+        element::{Group, Style, Text as TextElement},
+        Text as TextNode,
+    },
+    Document,
+};
+
+use crate::{
+    algorithm::algorithm::Algorithm,
+    puzzle::{
+        color_scheme::{multi_layer::MultiLayerColorScheme, ColorScheme},
+        grids::Grids,
+        render::{Renderer, RendererError},
+        sliding_puzzle::SlidingPuzzle,
+    },
+};
+
+/// A puzzle to be drawn by a [`SheetRenderer`], together with the lines of its caption.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SheetItem<Puzzle> {
+    puzzle: Puzzle,
+    caption: Vec<String>,
+}
+
+impl<Puzzle> SheetItem<Puzzle> {
+    /// Creates a new [`SheetItem`] with no caption.
+    #[must_use]
+    pub fn new(puzzle: Puzzle) -> Self {
+        Self {
+            puzzle,
+            caption: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`SheetItem`] for scramble number `number`, with a caption containing the
+    /// scramble number and the scramble algorithm.
+    #[must_use]
+    pub fn scramble(number: usize, puzzle: Puzzle, scramble: &Algorithm) -> Self {
+        Self::new(puzzle)
+            .caption_line(format!("#{number}"))
+            .caption_line(scramble.display_short_spaced().to_string())
+    }
+
+    /// Adds a line of text to the caption.
+    #[must_use]
+    pub fn caption_line(mut self, line: String) -> Self {
+        self.caption.push(line);
+        self
+    }
+
+    /// The puzzle.
+    #[must_use]
+    pub fn puzzle(&self) -> &Puzzle {
+        &self.puzzle
+    }
+
+    /// The lines of the caption.
+    #[must_use]
+    pub fn caption(&self) -> &[String] {
+        &self.caption
+    }
+}
+
+/// Draws many puzzles on one or more pages, arranged in a grid, using a [`Renderer`] to draw each
+/// puzzle.
+///
+/// Puzzles are laid out in rows of [`SheetRenderer::columns`] puzzles, with each caption drawn
+/// below its puzzle. Every cell of the grid has the same size, which is large enough to fit the
+/// largest puzzle and the longest caption.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SheetRenderer<
+    'a,
+    S: ColorScheme = Box<dyn ColorScheme + 'a>,
+    U: ColorScheme = Box<dyn ColorScheme + 'a>,
+    T: ColorScheme = Box<dyn ColorScheme + 'a>,
+    B: ColorScheme = Box<dyn ColorScheme + 'a>,
+    M: MultiLayerColorScheme = Box<dyn MultiLayerColorScheme + 'a>,
+    G: Grids = Box<dyn Grids + 'a>,
+> {
+    renderer: Renderer<'a, S, U, T, B, M, G>,
+    columns: usize,
+    spacing: f32,
+    margin: f32,
+    caption_font_size: f32,
+    page_size: Option<(f32, f32)>,
+}
+
+impl<
+        'a,
+        S: ColorScheme,
+        U: ColorScheme,
+        T: ColorScheme,
+        B: ColorScheme,
+        M: MultiLayerColorScheme,
+        G: Grids,
+    > SheetRenderer<'a, S, U, T, B, M, G>
+{
+    /// Create a new [`SheetRenderer`] that draws each puzzle using `renderer`. The default is 4
+    /// columns, with 20 pixels of spacing and margin, 16 pixel captions, and a single page.
+    #[must_use]
+    pub fn new(renderer: Renderer<'a, S, U, T, B, M, G>) -> Self {
+        Self {
+            renderer,
+            columns: 4,
+            spacing: 20.0,
+            margin: 20.0,
+            caption_font_size: 16.0,
+            page_size: None,
+        }
+    }
+
+    /// Set the number of puzzles in each row.
+    #[must_use]
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    /// Set the space between puzzles in pixels.
+    #[must_use]
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing.max(0.0);
+        self
+    }
+
+    /// Set the space around the edge of each page in pixels.
+    #[must_use]
+    pub fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin.max(0.0);
+        self
+    }
+
+    /// Set the font size of the captions.
+    #[must_use]
+    pub fn caption_font_size(mut self, size: f32) -> Self {
+        self.caption_font_size = size.max(0.0);
+        self
+    }
+
+    /// Set the size of each page in pixels. Puzzles that do not fit on a page are moved to the
+    /// next page, but each page has at least one row.
+    ///
+    /// If no page size is set, all puzzles are drawn on a single page that is large enough to fit
+    /// them.
+    #[must_use]
+    pub fn page_size(mut self, width: f32, height: f32) -> Self {
+        self.page_size = Some((width, height));
+        self
+    }
+
+    /// Draws `items` as SVG images, one for each page.
+    pub fn render<Puzzle>(
+        &self,
+        items: &[SheetItem<Puzzle>],
+    ) -> Result<Vec<Document>, RendererError>
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
+        let line_height = self.caption_font_size * 1.25;
+
+        // Size of the largest puzzle, and the height of the longest caption
+        let (image_w, image_h) = items
+            .iter()
+            .map(|item| self.renderer.image_size(item.puzzle.size()))
+            .fold((0.0f32, 0.0f32), |(w, h), (w2, h2)| (w.max(w2), h.max(h2)));
+        let caption_lines = items.iter().map(|item| item.caption.len()).max();
+        let caption_h = caption_lines.unwrap_or_default() as f32 * line_height;
+
+        let (cell_w, cell_h) = (image_w, image_h + caption_h);
+
+        let rows_per_page = self.page_size.map_or(usize::MAX, |(_, page_h)| {
+            let rows = (page_h - 2.0 * self.margin + self.spacing) / (cell_h + self.spacing);
+            (rows.floor() as usize).max(1)
+        });
+        let items_per_page = rows_per_page.saturating_mul(self.columns);
+
+        let style = self.renderer.style_string();
+
+        let mut pages = Vec::new();
+
+        for page_items in items.chunks(items_per_page.max(1)) {
+            let rows = page_items.len().div_ceil(self.columns);
+
+            let (page_w, page_h) = self.page_size.unwrap_or_else(|| {
+                let columns = self.columns.min(page_items.len());
+                (
+                    2.0 * self.margin + columns as f32 * (cell_w + self.spacing) - self.spacing,
+                    2.0 * self.margin + rows as f32 * (cell_h + self.spacing) - self.spacing,
+                )
+            });
+
+            let mut doc = Document::new()
+                .add(Style::new(style.as_str()))
+                .set("width", page_w)
+                .set("height", page_h);
+            if let Some(defs) = self.renderer.definitions() {
+                doc = doc.add(defs);
+            }
+
+            for (i, item) in page_items.iter().enumerate() {
+                let (col, row) = ((i % self.columns) as f32, (i / self.columns) as f32);
+                let x = self.margin + col * (cell_w + self.spacing);
+                let y = self.margin + row * (cell_h + self.spacing);
+
+                // Center the puzzle horizontally within the cell
+                let (w, h) = self.renderer.image_size(item.puzzle.size());
+                let puzzle = self.renderer.group(&item.puzzle)?.set(
+                    "transform",
+                    format!("translate({},{y})", x + (cell_w - w) / 2.0),
+                );
+
+                let mut cell = Group::new().add(puzzle);
+                for (j, line) in item.caption.iter().enumerate() {
+                    cell = cell.add(
+                        TextElement::new("")
+                            .set("class", "caption")
+                            .set("x", x + cell_w / 2.0)
+                            .set("y", y + h + line_height * (j as f32 + 0.5))
+                            // Inline style, so that it takes priority over the font size of `Text`
+                            .set("style", format!("font-size: {}px", self.caption_font_size))
+                            .add(TextNode::new(line.as_str())),
+                    );
+                }
+
+                doc = doc.add(cell);
+            }
+
+            pages.push(doc);
+        }
+
+        Ok(pages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::puzzle::{
+        color_scheme::Black,
+        puzzle::Puzzle,
+        render::{Image, RendererBuilder},
+        size::Size,
+    };
+
+    fn items(n: usize) -> Vec<SheetItem<Puzzle>> {
+        let scramble = Algorithm::from_str("DRUL").unwrap();
+        (1..=n)
+            .map(|i| {
+                let mut puzzle = Puzzle::new(Size::new(3, 3).unwrap());
+                puzzle.apply_alg(&scramble);
+                SheetItem::scramble(i, puzzle, &scramble)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_single_page() {
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black)).build();
+        let sheet = SheetRenderer::new(renderer).columns(2);
+
+        let pages = sheet.render(&items(3)).unwrap();
+        assert_eq!(pages.len(), 1);
+
+        let page = pages[0].to_string();
+        assert_eq!(page.matches(r#"class="caption""#).count(), 6);
+        assert!(page.contains("#3"));
+        assert!(page.contains("D R U L"));
+
+        // 2 columns of 225 pixels, 2 rows of 225 + 2 * 20 pixels, plus margins and spacing
+        assert!(page.contains(r#"width="510""#));
+        assert!(page.contains(r#"height="590""#));
+    }
+
+    #[test]
+    fn test_definitions() {
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black))
+            .image(Image::Base64 {
+                data: "AAAA",
+                format: "png",
+            })
+            .build();
+        let sheet = SheetRenderer::new(renderer).columns(2);
+
+        let page = sheet.render(&items(3)).unwrap()[0].to_string();
+        assert_eq!(page.matches("<clipPath").count(), 1);
+        assert_eq!(page.matches("clip-path=").count(), 3 * 8);
+    }
+
+    #[test]
+    fn test_pages() {
+        let renderer = RendererBuilder::with_dyn_scheme(Box::new(Black)).build();
+        let sheet = SheetRenderer::new(renderer)
+            .columns(2)
+            .page_size(600.0, 400.0);
+
+        let pages = sheet.render(&items(5)).unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(
+            pages
+                .iter()
+                .map(|page| page.to_string().matches(r#"class="caption""#).count())
+                .collect::<Vec<_>>(),
+            vec![4, 4, 2]
+        );
+    }
+}
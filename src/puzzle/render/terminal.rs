@@ -0,0 +1,368 @@
+//! Defines the [`TerminalRenderer`] struct, for drawing [`SlidingPuzzle`]s in a terminal using ANSI
+//! escape sequences.
+
+use std::fmt::{Display, Write as _};
+
+use num_traits::Zero as _;
+use palette::rgb::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::puzzle::{color_scheme::ColorScheme, sliding_puzzle::SlidingPuzzle};
+
+/// The colors that can be used by a [`TerminalRenderer`].
+///
+/// The default value is [`ColorMode::TrueColor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColorMode {
+    /// 24-bit colors. Supported by most modern terminals.
+    #[default]
+    TrueColor,
+    /// Colors from the standard 256 color palette. Each color is replaced by the closest color in
+    /// the palette.
+    Ansi256,
+    /// No colors. No escape sequences are written, so the output can be used anywhere that plain
+    /// text is expected.
+    Monochrome,
+}
+
+impl ColorMode {
+    /// The escape sequence that sets the foreground (`background == false`) or background
+    /// (`background == true`) color to `color`.
+    fn escape(self, color: Rgba, background: bool) -> String {
+        let color: Rgba<_, u8> = color.into_format();
+        let (r, g, b) = (color.red, color.green, color.blue);
+        let layer = if background { 48 } else { 38 };
+
+        match self {
+            Self::TrueColor => format!("\x1b[{layer};2;{r};{g};{b}m"),
+            Self::Ansi256 => format!("\x1b[{layer};5;{}m", ansi_256((r, g, b))),
+            Self::Monochrome => String::new(),
+        }
+    }
+}
+
+/// The index of the color in the 256 color palette that is closest to `(r, g, b)`.
+fn ansi_256((r, g, b): (u8, u8, u8)) -> u8 {
+    // Levels of each channel in the 6x6x6 color cube (indices 16 to 231)
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let nearest_level = |c: u8| {
+        (0..6u8)
+            .min_by_key(|&i| LEVELS[i as usize].abs_diff(c))
+            .unwrap_or_default()
+    };
+
+    let dist = |(r2, g2, b2): (u8, u8, u8)| {
+        [(r, r2), (g, g2), (b, b2)]
+            .into_iter()
+            .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+            .sum::<u32>()
+    };
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (
+        16 + 36 * ri + 6 * gi + bi,
+        dist((
+            LEVELS[ri as usize],
+            LEVELS[gi as usize],
+            LEVELS[bi as usize],
+        )),
+    );
+
+    // Grayscale ramp (indices 232 to 255), with levels 8, 18, ..., 238
+    let gray = (0..24u8)
+        .map(|i| {
+            let level = 8 + 10 * i;
+            (232 + i, dist((level, level, level)))
+        })
+        .min_by_key(|&(_, d)| d)
+        .unwrap_or(cube);
+
+    if gray.1 < cube.1 {
+        gray.0
+    } else {
+        cube.0
+    }
+}
+
+/// Draws a [`SlidingPuzzle`] as text with ANSI escape sequences, for displaying in a terminal.
+///
+/// Each piece is drawn as a cell of text with the background color given by the color scheme. The
+/// text color is given by the text scheme if there is one, and is otherwise black or white,
+/// whichever is more readable on the background color. The gap is drawn as an empty cell.
+///
+/// # Example
+///
+/// ```
+/// use slidy::puzzle::{
+///     color_scheme::Black,
+///     puzzle::Puzzle,
+///     render::terminal::{ColorMode, TerminalRenderer},
+/// };
+///
+/// fn main() {
+///     let p = Puzzle::default();
+///     let renderer =
+///         TerminalRenderer::with_dyn_scheme(Box::new(Black)).color_mode(ColorMode::Monochrome);
+///     let rows = [
+///         "  1   2   3   4 ",
+///         "  5   6   7   8 ",
+///         "  9  10  11  12 ",
+///         " 13  14  15     ",
+///     ];
+///     assert_eq!(renderer.render(&p), rows.join("\n"));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TerminalRenderer<
+    S: ColorScheme = Box<dyn ColorScheme>,
+    T: ColorScheme = Box<dyn ColorScheme>,
+> {
+    scheme: S,
+    text_scheme: Option<T>,
+    cell_width: Option<usize>,
+    borders: bool,
+    color_mode: ColorMode,
+}
+
+impl TerminalRenderer {
+    /// Create a new [`TerminalRenderer`] with the default color scheme.
+    #[must_use]
+    pub fn with_dyn_scheme(scheme: Box<dyn ColorScheme>) -> Self {
+        Self::with_scheme(scheme)
+    }
+}
+
+impl<S: ColorScheme, T: ColorScheme> TerminalRenderer<S, T> {
+    /// Create a new [`TerminalRenderer`]. The default is to use [`ColorMode::TrueColor`] with no
+    /// borders, and cells that are 2 characters wider than the largest piece number.
+    #[must_use]
+    pub fn with_scheme(scheme: S) -> Self {
+        Self {
+            scheme,
+            text_scheme: None,
+            cell_width: None,
+            borders: false,
+            color_mode: ColorMode::TrueColor,
+        }
+    }
+
+    /// Set the color scheme.
+    #[must_use]
+    pub fn scheme(mut self, scheme: S) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Set the text color scheme.
+    #[must_use]
+    pub fn text_scheme(mut self, scheme: T) -> Self {
+        self.text_scheme = Some(scheme);
+        self
+    }
+
+    /// Set the width of each cell in characters. Piece numbers are right-aligned, followed by a
+    /// space.
+    ///
+    /// The width is increased when drawing a puzzle if it is too small for the largest piece
+    /// number and the space after it, so that cells never overflow.
+    #[must_use]
+    pub fn cell_width(mut self, width: usize) -> Self {
+        self.cell_width = Some(width.max(1));
+        self
+    }
+
+    /// Set whether lines are drawn around each cell, using box-drawing characters.
+    #[must_use]
+    pub fn borders(mut self, borders: bool) -> Self {
+        self.borders = borders;
+        self
+    }
+
+    /// Set the color mode.
+    #[must_use]
+    pub fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
+    }
+
+    /// Draws `puzzle` as a string, with rows separated by new lines.
+    #[must_use]
+    pub fn render<Puzzle>(&self, puzzle: &Puzzle) -> String
+    where
+        Puzzle: SlidingPuzzle,
+        Puzzle::Piece: Display,
+    {
+        let size = puzzle.size();
+        let (width, height) = size.into();
+
+        // Number of digits of the largest piece, plus a space
+        let min_cell_width = puzzle.num_pieces().max(1).ilog10() as usize + 2;
+        let cell_width = self
+            .cell_width
+            .map_or(min_cell_width + 1, |width| width.max(min_cell_width));
+
+        // Horizontal border line, e.g. "├───┼───┤"
+        let border_line = |left: char, middle: char, right: char| {
+            let mut line = String::new();
+            line.push(left);
+            for x in 0..width {
+                if x != 0 {
+                    line.push(middle);
+                }
+                line.extend(std::iter::repeat_n('─', cell_width));
+            }
+            line.push(right);
+            line
+        };
+
+        let mut s = String::new();
+
+        if self.borders {
+            s.push_str(&border_line('┌', '┬', '┐'));
+            s.push('\n');
+        }
+
+        for y in 0..height {
+            if self.borders {
+                s.push('│');
+            }
+
+            for x in 0..width {
+                let piece = puzzle.piece_at_xy((x, y));
+
+                if piece == Puzzle::Piece::zero() {
+                    s.extend(std::iter::repeat_n(' ', cell_width));
+                } else {
+                    let solved_pos = puzzle.solved_pos_xy(piece);
+                    let background = self.scheme.color(size, solved_pos);
+                    let foreground = self.text_scheme.as_ref().map_or_else(
+                        || contrasting_color(background),
+                        |scheme| scheme.color(size, solved_pos),
+                    );
+
+                    let text = format!("{piece:>w$} ", w = cell_width - 1);
+                    if self.color_mode == ColorMode::Monochrome {
+                        s.push_str(&text);
+                    } else {
+                        let _ = write!(
+                            s,
+                            "{}{}{text}\x1b[0m",
+                            self.color_mode.escape(background, true),
+                            self.color_mode.escape(foreground, false),
+                        );
+                    }
+                }
+
+                if self.borders {
+                    s.push('│');
+                }
+            }
+
+            if y != height - 1 {
+                s.push('\n');
+                if self.borders {
+                    s.push_str(&border_line('├', '┼', '┤'));
+                    s.push('\n');
+                }
+            }
+        }
+
+        if self.borders {
+            s.push('\n');
+            s.push_str(&border_line('└', '┴', '┘'));
+        }
+
+        s
+    }
+}
+
+/// Black or white, whichever is more readable on a background of color `background`.
+fn contrasting_color(background: Rgba) -> Rgba {
+    let luminance = 0.2126 * background.red + 0.7152 * background.green + 0.0722 * background.blue;
+
+    if luminance > 0.5 {
+        Rgba::new(0.0, 0.0, 0.0, 1.0)
+    } else {
+        Rgba::new(1.0, 1.0, 1.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::puzzle::{
+        color_scheme::{Black, Scheme},
+        coloring::Monochrome,
+        label::label::Rows,
+        puzzle::Puzzle,
+    };
+
+    #[test]
+    fn test_ansi_256() {
+        assert_eq!(ansi_256((0, 0, 0)), 16);
+        assert_eq!(ansi_256((255, 255, 255)), 231);
+        assert_eq!(ansi_256((255, 0, 0)), 196);
+        assert_eq!(ansi_256((128, 128, 128)), 244);
+        assert_eq!(ansi_256((0, 95, 135)), 24);
+    }
+
+    #[test]
+    fn test_monochrome_borders() {
+        let p = Puzzle::from_str("1 2/3 0").unwrap();
+        let renderer = TerminalRenderer::with_scheme(Black)
+            .text_scheme(Black)
+            .color_mode(ColorMode::Monochrome)
+            .borders(true)
+            .cell_width(3);
+
+        let rows = [
+            "┌───┬───┐",
+            "│ 1 │ 2 │",
+            "├───┼───┤",
+            "│ 3 │   │",
+            "└───┴───┘",
+        ];
+        assert_eq!(renderer.render(&p), rows.join("\n"));
+    }
+
+    #[test]
+    fn test_cell_width_too_small() {
+        let p = Puzzle::from_str("1 2 3 4/5 6 7 8/9 10 11 12/13 14 15 0").unwrap();
+        let renderer = TerminalRenderer::with_scheme(Black)
+            .text_scheme(Black)
+            .color_mode(ColorMode::Monochrome)
+            .borders(true)
+            .cell_width(1);
+
+        let s = renderer.render(&p);
+        let rows = s.lines().collect::<Vec<_>>();
+        assert_eq!(rows[0], "┌───┬───┬───┬───┐");
+        assert_eq!(rows[1], "│ 1 │ 2 │ 3 │ 4 │");
+        assert_eq!(rows[7], "│13 │14 │15 │   │");
+        assert!(rows.iter().all(|row| row.chars().count() == 17));
+    }
+
+    #[test]
+    fn test_true_color() {
+        let p = Puzzle::from_str("1 2/3 0").unwrap();
+        let scheme = Scheme::new(Rows, Monochrome::new(Rgba::new(1.0, 1.0, 0.0, 1.0)));
+        let renderer = TerminalRenderer::<_, Black>::with_scheme(scheme);
+
+        let s = renderer.render(&p);
+        assert_eq!(
+            s.lines().next(),
+            Some("\x1b[48;2;255;255;0m\x1b[38;2;0;0;0m 1 \x1b[0m\x1b[48;2;255;255;0m\x1b[38;2;0;0;0m 2 \x1b[0m")
+        );
+        assert!(s.ends_with("\x1b[0m   "));
+
+        let renderer = renderer.color_mode(ColorMode::Ansi256);
+        assert!(renderer
+            .render(&p)
+            .starts_with("\x1b[48;5;226m\x1b[38;5;16m 1 \x1b[0m"));
+    }
+}
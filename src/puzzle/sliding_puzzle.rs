@@ -7,9 +7,11 @@ use crate::{
     algorithm::{
         as_slice::AsAlgorithmSlice,
         direction::Direction,
+        moves::Moves,
         r#move::{position_move::PositionMove, r#move::Move, try_into_move::TryIntoMove as _},
     },
     puzzle::{
+        apply::{ApplyAlgError, States},
         label::label::{BijectiveLabel, RowGrids},
         size::Size,
         solvable::Solvable as _,
@@ -739,6 +741,46 @@ where
         }
     }
 
+    /// See [`SlidingPuzzle::apply_alg`].
+    ///
+    /// If a move can not be applied, the puzzle is left unchanged, and the returned error contains
+    /// the index of the move and the state of the puzzle just before it.
+    fn apply_alg_checked<'a, Alg>(&mut self, alg: &'a Alg) -> Result<(), ApplyAlgError<Self>>
+    where
+        Alg: AsAlgorithmSlice<'a>,
+        Self: Clone,
+    {
+        let mut puzzle = self.clone();
+        let mut stm_index = 0;
+
+        for (move_index, mv) in alg.as_slice().moves().enumerate() {
+            if !puzzle.can_apply_move(mv) {
+                return Err(ApplyAlgError::IllegalMove {
+                    move_index: move_index as u64,
+                    stm_index,
+                    mv,
+                    state: puzzle,
+                });
+            }
+
+            puzzle.apply_move(mv);
+            stm_index += mv.amount;
+        }
+
+        *self = puzzle;
+        Ok(())
+    }
+
+    /// Returns an iterator over the states of the puzzle after each move of `alg` is applied to a
+    /// copy of `self`. See [`States`].
+    fn states<'a, Alg>(&self, alg: &'a Alg) -> States<'a, Self>
+    where
+        Alg: AsAlgorithmSlice<'a>,
+        Self: Clone,
+    {
+        States::new(self.clone(), Moves::new(alg.as_slice()))
+    }
+
     /// Checks if it is possible to embed `self` into `puzzle`.
     #[must_use]
     fn can_embed_into(&self, puzzle: &Self) -> bool {
@@ -4,6 +4,8 @@
 //! [`slidy::label::label`]: ../label/label/index.html
 
 use itertools::Itertools as _;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::puzzle::{label::label::Label, sliding_puzzle::SlidingPuzzle};
 
@@ -15,6 +17,18 @@ pub trait SolvedState {
     where
         Puzzle: SlidingPuzzle,
         Self: Sized;
+
+    /// Checks if the piece at position `pos` of `puzzle` is solved.
+    ///
+    /// The default implementation checks if the piece is in its solved position.
+    #[must_use]
+    fn is_solved_at<Puzzle>(&self, puzzle: &Puzzle, pos: (u64, u64)) -> bool
+    where
+        Puzzle: SlidingPuzzle,
+        Self: Sized,
+    {
+        puzzle.solved_pos_xy(puzzle.piece_at_xy(pos)) == pos
+    }
 }
 
 impl<L: Label> SolvedState for L {
@@ -31,16 +45,103 @@ impl<L: Label> SolvedState for L {
         (0..w)
             .cartesian_product(0..h)
             .take(size.num_pieces() as usize)
-            .all(|(x, y)| {
-                // Label of piece in position (x, y)
-                let solved_pos = puzzle.solved_pos_xy(puzzle.piece_at_xy((x, y)));
-                let piece_label = self.try_position_label(size, solved_pos);
+            .all(|pos| self.is_solved_at(puzzle, pos))
+    }
+
+    fn is_solved_at<Puzzle>(&self, puzzle: &Puzzle, pos: (u64, u64)) -> bool
+    where
+        Puzzle: SlidingPuzzle,
+    {
+        let size = puzzle.size();
+
+        // Label of piece in position `pos`
+        let solved_pos = puzzle.solved_pos_xy(puzzle.piece_at_xy(pos));
+        let piece_label = self.try_position_label(size, solved_pos);
+
+        // Label of piece in position `pos` on a solved puzzle
+        let solved_label = self.try_position_label(size, pos);
+
+        piece_label == solved_label
+    }
+}
 
-                // Label of piece in position (x, y) on a solved puzzle
-                let solved_label = self.try_position_label(size, (x, y));
+/// A [`SolvedState`] where only some of the pieces need to be solved.
+///
+/// The puzzle is considered solved if every position whose label (as defined by the [`Label`] `L`)
+/// is one of the given labels contains a piece with the same label. The gap does not need to be in
+/// the bottom right corner.
+///
+/// For example, `Partial::new(RowGrids, vec![0, 1, 2, 3])` is solved when the first row of a 4x4
+/// puzzle is solved, and `Partial::new(Rows, vec![0])` is solved when the pieces in the first row
+/// are all in the first row, possibly in the wrong order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Partial<L: Label> {
+    label: L,
+    labels: Vec<u64>,
+}
 
-                piece_label == solved_label
-            })
+impl<L: Label> Partial<L> {
+    /// Creates a new [`Partial`] solved state, where positions with a label in `labels` need to be
+    /// solved.
+    #[must_use]
+    pub fn new(label: L, labels: Vec<u64>) -> Self {
+        Self { label, labels }
+    }
+
+    /// Returns a reference to the inner [`Label`].
+    #[must_use]
+    pub fn label(&self) -> &L {
+        &self.label
+    }
+
+    /// The labels of the positions that need to be solved.
+    #[must_use]
+    pub fn labels(&self) -> &[u64] {
+        &self.labels
+    }
+}
+
+impl<L: Label> SolvedState for Partial<L> {
+    fn is_solved<Puzzle>(&self, puzzle: &Puzzle) -> bool
+    where
+        Puzzle: SlidingPuzzle,
+    {
+        let size = puzzle.size();
+        let (w, h) = size.into();
+
+        (0..w).cartesian_product(0..h).all(|(x, y)| {
+            // Label of piece in position (x, y) on a solved puzzle
+            let Ok(solved_label) = self.label.try_position_label(size, (x, y)) else {
+                return false;
+            };
+
+            !self.labels.contains(&solved_label) || self.is_solved_at(puzzle, (x, y))
+        })
+    }
+
+    /// Checks if the piece at position `pos` of `puzzle` is solved. Returns `false` if `pos` is
+    /// not one of the positions that need to be solved.
+    fn is_solved_at<Puzzle>(&self, puzzle: &Puzzle, pos: (u64, u64)) -> bool
+    where
+        Puzzle: SlidingPuzzle,
+    {
+        let size = puzzle.size();
+
+        // Label of piece in position `pos` on a solved puzzle
+        let Ok(solved_label) = self.label.try_position_label(size, pos) else {
+            return false;
+        };
+
+        if !self.labels.contains(&solved_label) {
+            return false;
+        }
+
+        // Label of piece in position `pos`
+        let solved_pos = puzzle.solved_pos_xy(puzzle.piece_at_xy(pos));
+        let piece_label = self.label.try_position_label(size, solved_pos);
+
+        piece_label == Ok(solved_label)
     }
 }
 
@@ -243,4 +344,67 @@ mod tests {
         5: false: "3 17 5 1 6 4 18 7 2 13/8 12 11 15 10 14 9 16 19 0",
         6: false: "3 8 9 10/4 15 0 12/6 2 1 5/11 7 14 13",
     );
+
+    mod partial {
+        use crate::puzzle::{
+            label::label::{RowGrids, Rows, SplitFringe},
+            solved_state::{Partial, SolvedState as _},
+        };
+
+        use super::*;
+
+        #[test]
+        fn test_partial_first_row() {
+            let first_row = Partial::new(RowGrids, vec![0, 1, 2, 3]);
+            let p = Puzzle::from_str("1 2 3 4/5 0 7 8/9 10 11 12/13 14 15 6").unwrap();
+            assert!(first_row.is_solved(&p));
+            let p = Puzzle::from_str("1 2 4 3/5 6 7 8/9 10 11 12/13 14 15 0").unwrap();
+            assert!(!first_row.is_solved(&p));
+            let p = Puzzle::from_str("1 2 3 0/5 6 7 8/9 10 11 12/13 14 15 4").unwrap();
+            assert!(!first_row.is_solved(&p));
+        }
+
+        #[test]
+        fn test_partial_rows() {
+            let first_row = Partial::new(Rows, vec![0]);
+            let p = Puzzle::from_str("4 2 3 1/5 0 7 8/9 10 11 12/13 14 15 6").unwrap();
+            assert!(first_row.is_solved(&p));
+            let p = Puzzle::from_str("5 2 3 1/4 0 7 8/9 10 11 12/13 14 15 6").unwrap();
+            assert!(!first_row.is_solved(&p));
+        }
+
+        #[test]
+        fn test_partial_split_fringe() {
+            // Label 0 of `SplitFringe` is the top row, label 1 is the left column
+            let fringe = Partial::new(SplitFringe, vec![0, 1]);
+            let p = Puzzle::from_str("1 2 3 4/5 11 8 0/9 10 6 12/13 7 14 15").unwrap();
+            assert!(fringe.is_solved(&p));
+            let p = Puzzle::from_str("1 2 3 4/11 5 8 0/9 10 6 12/13 7 14 15").unwrap();
+            assert!(!fringe.is_solved(&p));
+        }
+
+        #[test]
+        fn test_partial_is_solved_at() {
+            let first_row = Partial::new(RowGrids, vec![0, 1, 2, 3]);
+            let p = Puzzle::from_str("1 2 4 3/5 6 7 8/9 10 11 12/13 14 15 0").unwrap();
+            assert!(first_row.is_solved_at(&p, (0, 0)));
+            assert!(!first_row.is_solved_at(&p, (2, 0)));
+            assert!(!first_row.is_solved_at(&p, (0, 1)));
+        }
+    }
+
+    mod is_solved_at {
+        use crate::puzzle::{label::label::Rows, solved_state::SolvedState as _};
+
+        use super::*;
+
+        #[test]
+        fn test_is_solved_at() {
+            let p = Puzzle::from_str("2 1 3/4 5 8/7 0 6").unwrap();
+            assert!(Rows.is_solved_at(&p, (0, 0)));
+            assert!(Rows.is_solved_at(&p, (1, 2)));
+            assert!(!Rows.is_solved_at(&p, (2, 1)));
+            assert!(!Rows.is_solved_at(&p, (2, 2)));
+        }
+    }
 }
@@ -0,0 +1,236 @@
+//! Defines the [`Symmetry`] type, the 8 symmetries of a rectangular puzzle.
+//!
+//! The symmetries transform positions in the same way as the label modifiers in
+//! [`label::symmetry`], so a puzzle transformed by a [`Symmetry`] is labelled by the corresponding
+//! label modifier.
+//!
+//! [`label::symmetry`]: ../label/symmetry/index.html
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    algorithm::{algorithm::Algorithm, direction::Direction, r#move::r#move::Move},
+    puzzle::size::Size,
+};
+
+/// One of the 8 symmetries of a square, applied to a (possibly non-square) puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Symmetry {
+    /// The identity symmetry.
+    Id,
+    /// Rotation clockwise by 90 degrees. See [`RotateCw`].
+    ///
+    /// [`RotateCw`]: ../label/symmetry/struct.RotateCw.html
+    RotateCw,
+    /// Rotation anticlockwise by 90 degrees. See [`RotateCcw`].
+    ///
+    /// [`RotateCcw`]: ../label/symmetry/struct.RotateCcw.html
+    RotateCcw,
+    /// Rotation by 180 degrees. See [`RotateHalf`].
+    ///
+    /// [`RotateHalf`]: ../label/symmetry/struct.RotateHalf.html
+    RotateHalf,
+    /// Reflection that swaps the top and bottom rows. See [`ReflectVertical`].
+    ///
+    /// [`ReflectVertical`]: ../label/symmetry/struct.ReflectVertical.html
+    ReflectVertical,
+    /// Reflection that swaps the left and right columns. See [`ReflectHorizontal`].
+    ///
+    /// [`ReflectHorizontal`]: ../label/symmetry/struct.ReflectHorizontal.html
+    ReflectHorizontal,
+    /// Reflection in the diagonal line from top left to bottom right. See [`ReflectDiagonal`].
+    ///
+    /// [`ReflectDiagonal`]: ../label/symmetry/struct.ReflectDiagonal.html
+    ReflectDiagonal,
+    /// Reflection in the diagonal line from bottom left to top right. See
+    /// [`ReflectAntidiagonal`].
+    ///
+    /// [`ReflectAntidiagonal`]: ../label/symmetry/struct.ReflectAntidiagonal.html
+    ReflectAntidiagonal,
+}
+
+impl Symmetry {
+    /// All 8 symmetries.
+    pub const ALL: [Self; 8] = [
+        Self::Id,
+        Self::RotateCw,
+        Self::RotateCcw,
+        Self::RotateHalf,
+        Self::ReflectVertical,
+        Self::ReflectHorizontal,
+        Self::ReflectDiagonal,
+        Self::ReflectAntidiagonal,
+    ];
+
+    /// The symmetry that undoes `self`.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::RotateCw => Self::RotateCcw,
+            Self::RotateCcw => Self::RotateCw,
+            Self::Id
+            | Self::RotateHalf
+            | Self::ReflectVertical
+            | Self::ReflectHorizontal
+            | Self::ReflectDiagonal
+            | Self::ReflectAntidiagonal => *self,
+        }
+    }
+
+    /// Checks if the symmetry swaps the width and height of a puzzle.
+    #[must_use]
+    pub fn is_transposing(&self) -> bool {
+        matches!(
+            self,
+            Self::RotateCw | Self::RotateCcw | Self::ReflectDiagonal | Self::ReflectAntidiagonal
+        )
+    }
+
+    /// The size of a puzzle of size `size` after applying the symmetry.
+    #[must_use]
+    pub fn transform_size(&self, size: Size) -> Size {
+        if self.is_transposing() {
+            size.transpose()
+        } else {
+            size
+        }
+    }
+
+    /// The position that `(x, y)` on a puzzle of size `size` is mapped to by the symmetry.
+    #[must_use]
+    pub fn transform_position(&self, size: Size, (x, y): (u64, u64)) -> (u64, u64) {
+        let (width, height) = size.into();
+        match self {
+            Self::Id => (x, y),
+            Self::RotateCw => (height - 1 - y, x),
+            Self::RotateCcw => (y, width - 1 - x),
+            Self::RotateHalf => (width - 1 - x, height - 1 - y),
+            Self::ReflectVertical => (x, height - 1 - y),
+            Self::ReflectHorizontal => (width - 1 - x, y),
+            Self::ReflectDiagonal => (y, x),
+            Self::ReflectAntidiagonal => (height - 1 - y, width - 1 - x),
+        }
+    }
+
+    /// The algorithm that moves the gap from the image of the solved gap position of a puzzle of
+    /// size `size` back to the solved gap position, on the transformed puzzle.
+    ///
+    /// If `alg` solves a [`Puzzle`] `p`, then `alg.apply_symmetry(s) + s.gap_correction(size)`
+    /// solves `p.apply_symmetry(s)`. See [`Puzzle::apply_symmetry`].
+    ///
+    /// [`Puzzle`]: ../puzzle/struct.Puzzle.html
+    /// [`Puzzle::apply_symmetry`]: ../puzzle/struct.Puzzle.html#method.apply_symmetry
+    #[must_use]
+    pub fn gap_correction(&self, size: Size) -> Algorithm {
+        let (width, height) = self.transform_size(size).into();
+        let (x, y) = self.transform_position(size, (size.width() - 1, size.height() - 1));
+
+        [
+            Move::new_nonzero(Direction::Left, width - 1 - x),
+            Move::new_nonzero(Direction::Up, height - 1 - y),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::puzzle::{
+        label::{
+            label::{Label as _, RowGrids},
+            symmetry::{
+                Id, ReflectAntidiagonal, ReflectDiagonal, ReflectHorizontal, ReflectVertical,
+                RotateCcw, RotateCw, RotateHalf,
+            },
+        },
+        puzzle::Puzzle,
+        sliding_puzzle::SlidingPuzzle as _,
+    };
+
+    #[test]
+    fn test_matches_labels() {
+        let size = Size::new(4, 3).unwrap();
+
+        for sym in Symmetry::ALL {
+            let new_size = sym.transform_size(size);
+            for (y, x) in itertools::iproduct!(0..3, 0..4) {
+                let pos = sym.transform_position(size, (x, y));
+                let label = match sym {
+                    Symmetry::Id => Id(RowGrids).position_label(new_size, pos),
+                    Symmetry::RotateCw => RotateCw(RowGrids).position_label(new_size, pos),
+                    Symmetry::RotateCcw => RotateCcw(RowGrids).position_label(new_size, pos),
+                    Symmetry::RotateHalf => RotateHalf(RowGrids).position_label(new_size, pos),
+                    Symmetry::ReflectVertical => {
+                        ReflectVertical(RowGrids).position_label(new_size, pos)
+                    }
+                    Symmetry::ReflectHorizontal => {
+                        ReflectHorizontal(RowGrids).position_label(new_size, pos)
+                    }
+                    Symmetry::ReflectDiagonal => {
+                        ReflectDiagonal(RowGrids).position_label(new_size, pos)
+                    }
+                    Symmetry::ReflectAntidiagonal => {
+                        ReflectAntidiagonal(RowGrids).position_label(new_size, pos)
+                    }
+                };
+                assert_eq!(label, RowGrids.position_label(size, (x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let size = Size::new(4, 3).unwrap();
+
+        for sym in Symmetry::ALL {
+            let inv = sym.inverse();
+            assert_eq!(inv.transform_size(sym.transform_size(size)), size);
+            for (y, x) in itertools::iproduct!(0..3, 0..4) {
+                let pos = sym.transform_position(size, (x, y));
+                assert_eq!(
+                    inv.transform_position(sym.transform_size(size), pos),
+                    (x, y)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gap_correction() {
+        let size = Size::new(4, 3).unwrap();
+        assert_eq!(Symmetry::Id.gap_correction(size), Algorithm::new());
+        assert_eq!(
+            Symmetry::RotateCw.gap_correction(size),
+            Algorithm::from_str("L2").unwrap()
+        );
+        assert_eq!(
+            Symmetry::RotateHalf.gap_correction(size),
+            Algorithm::from_str("L3U2").unwrap()
+        );
+        assert_eq!(
+            Symmetry::ReflectAntidiagonal.gap_correction(size),
+            Algorithm::from_str("L2U3").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_solutions_carry_over() {
+        let scramble = Algorithm::from_str("DRDRULDRURDLLURULDDR").unwrap();
+        let mut puzzle = Puzzle::new(Size::new(4, 3).unwrap());
+        puzzle.apply_alg(&scramble);
+        let solution = scramble.inverse();
+
+        for sym in Symmetry::ALL {
+            let p = puzzle.apply_symmetry(sym);
+            let s = solution.apply_symmetry(sym) + sym.gap_correction(puzzle.size());
+            assert!(s.is_solution_of(p), "{sym:?}");
+        }
+    }
+}
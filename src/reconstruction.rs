@@ -0,0 +1,6 @@
+//! Contains everything related to reconstructions of solves (timed sequences of moves applied to a
+//! scrambled puzzle).
+
+pub mod efficiency;
+pub mod reconstruction;
+pub mod splits;
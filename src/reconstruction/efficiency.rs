@@ -0,0 +1,297 @@
+//! Defines functions for measuring the efficiency of a solution, by comparing each segment of the
+//! solution against the optimal distance to the solved state.
+
+use itertools::Itertools as _;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    algorithm::{
+        algorithm::Algorithm,
+        metric::{Metric, Mtm, Stm},
+    },
+    puzzle::{puzzle::Puzzle, sliding_puzzle::SlidingPuzzle as _, solved_state::SolvedState},
+    solver::{
+        heuristic::Heuristic,
+        solver::{Solver, SolverError},
+    },
+};
+
+/// Error type for [`efficiency`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EfficiencyError {
+    /// Errors from [`Solver`].
+    #[error("SolverError: {0}")]
+    SolverError(#[from] SolverError),
+
+    /// Returned when the solution can not be applied to the scramble.
+    #[error("IllegalAlgorithm: the solution can not be applied to the scramble")]
+    IllegalAlgorithm,
+
+    /// Returned when the checkpoints are not strictly increasing, or a checkpoint is not strictly
+    /// between 0 and the length of the solution.
+    #[error("InvalidCheckpoint: checkpoint {0} is out of order or out of range")]
+    InvalidCheckpoint(u64),
+}
+
+/// The efficiency of one segment of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Segment {
+    /// The number of single tile moves at the start of the segment.
+    pub start: u64,
+
+    /// The number of single tile moves at the end of the segment.
+    pub end: u64,
+
+    /// The length of the segment in [`Stm`].
+    pub stm: u64,
+
+    /// The length of the segment in [`Mtm`]. A move that is split between two segments only
+    /// counts in the segment where it ends, so the lengths of all segments add up to the length
+    /// of the solution.
+    pub mtm: u64,
+
+    /// The optimal distance to the solved state at the start of the segment, in [`Stm`].
+    pub optimal_before_stm: u64,
+
+    /// The optimal distance to the solved state at the end of the segment, in [`Stm`].
+    pub optimal_after_stm: u64,
+
+    /// The optimal distance to the solved state at the start of the segment, in [`Mtm`].
+    pub optimal_before_mtm: u64,
+
+    /// The optimal distance to the solved state at the end of the segment, in [`Mtm`].
+    pub optimal_after_mtm: u64,
+
+    /// The number of moves in the segment, in [`Stm`], that did not reduce the optimal [`Stm`]
+    /// distance to the solved state.
+    pub waste_stm: u64,
+
+    /// The number of moves in the segment, in [`Mtm`], that did not reduce the optimal [`Mtm`]
+    /// distance to the solved state.
+    pub waste_mtm: u64,
+}
+
+/// Splits `solution` into segments at the given checkpoints, and computes the number of moves that
+/// were wasted in each segment, compared to optimal, in both [`Stm`] and [`Mtm`].
+///
+/// The checkpoints are numbers of single tile moves, and must be strictly increasing and strictly
+/// between 0 and the length of the solution in [`Stm`]. The start and end of the solution are
+/// always used as checkpoints, so `n` checkpoints produce `n + 1` segments.
+///
+/// The optimal distances are computed with `stm_solver` and `mtm_solver`, e.g. a [`Solver4x4Stm`]
+/// and a [`Solver4x4Mtm`].
+///
+/// [`Solver4x4Stm`]: ../../solver/type.Solver4x4Stm.html
+/// [`Solver4x4Mtm`]: ../../solver/type.Solver4x4Mtm.html
+pub fn efficiency<StmSv, StmT, StmS, StmH, MtmSv, MtmT, MtmS, MtmH>(
+    stm_solver: &mut StmSv,
+    mtm_solver: &mut MtmSv,
+    scramble: &Puzzle,
+    solution: &Algorithm,
+    checkpoints: &[u64],
+) -> Result<Vec<Segment>, EfficiencyError>
+where
+    StmSv: Solver<Puzzle, StmT, StmS, StmH, Stm>,
+    StmS: SolvedState,
+    StmH: Heuristic<Puzzle, StmT, StmS, Stm>,
+    MtmSv: Solver<Puzzle, MtmT, MtmS, MtmH, Mtm>,
+    MtmS: SolvedState,
+    MtmH: Heuristic<Puzzle, MtmT, MtmS, Mtm>,
+{
+    if !scramble.can_apply_alg(solution) {
+        return Err(EfficiencyError::IllegalAlgorithm);
+    }
+
+    let len = solution.len_stm::<u64>();
+
+    let mut points = vec![0];
+    for &c in checkpoints {
+        if c <= points[points.len() - 1] || c >= len {
+            return Err(EfficiencyError::InvalidCheckpoint(c));
+        }
+        points.push(c);
+    }
+    points.push(len);
+
+    // The number of moves that end within the first `p` single tile moves of the solution
+    let moves_ended_by = |p: u64| {
+        solution
+            .moves()
+            .iter()
+            .scan(0, |total, mv| {
+                *total += mv.amount();
+                Some(*total)
+            })
+            .take_while(|&total| total <= p)
+            .count() as u64
+    };
+
+    let stm_distances = distances(stm_solver, scramble, solution, &points)?;
+    let mtm_distances = distances(mtm_solver, scramble, solution, &points)?;
+
+    let segments = points
+        .into_iter()
+        .tuple_windows()
+        .zip(stm_distances.into_iter().tuple_windows())
+        .zip(mtm_distances.into_iter().tuple_windows())
+        .map(
+            |(
+                ((start, end), (optimal_before_stm, optimal_after_stm)),
+                (optimal_before_mtm, optimal_after_mtm),
+            )| {
+                let stm = end - start;
+                let mtm = moves_ended_by(end) - moves_ended_by(start);
+
+                Segment {
+                    start,
+                    end,
+                    stm,
+                    mtm,
+                    optimal_before_stm,
+                    optimal_after_stm,
+                    optimal_before_mtm,
+                    optimal_after_mtm,
+                    waste_stm: stm
+                        .saturating_sub(optimal_before_stm.saturating_sub(optimal_after_stm)),
+                    waste_mtm: mtm
+                        .saturating_sub(optimal_before_mtm.saturating_sub(optimal_after_mtm)),
+                }
+            },
+        )
+        .collect();
+
+    Ok(segments)
+}
+
+/// The optimal distance to the solved state in metric `M` after applying the first `p` single tile
+/// moves of `solution` to `scramble`, for each `p` in `points`.
+fn distances<Sv, T, S, H, M>(
+    solver: &mut Sv,
+    scramble: &Puzzle,
+    solution: &Algorithm,
+    points: &[u64],
+) -> Result<Vec<u64>, EfficiencyError>
+where
+    Sv: Solver<Puzzle, T, S, H, M>,
+    S: SolvedState,
+    H: Heuristic<Puzzle, T, S, M>,
+    M: Metric,
+{
+    points
+        .iter()
+        .map(|&p| {
+            let mut puzzle = scramble.clone();
+            puzzle.apply_alg(&solution.try_slice(0..p).unwrap());
+            Ok(solver.solve(&puzzle)?.len::<M, u64>())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::solver::{Solver3x3Mtm, Solver3x3Stm};
+
+    #[test]
+    fn test_efficiency_optimal() {
+        let mut stm_solver = Solver3x3Stm::new();
+        let mut mtm_solver = Solver3x3Mtm::new();
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/0 7 8").unwrap();
+        let solution = Algorithm::from_str("L2").unwrap();
+
+        let segments =
+            efficiency(&mut stm_solver, &mut mtm_solver, &scramble, &solution, &[1]).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment {
+                    start: 0,
+                    end: 1,
+                    stm: 1,
+                    mtm: 0,
+                    optimal_before_stm: 2,
+                    optimal_after_stm: 1,
+                    optimal_before_mtm: 1,
+                    optimal_after_mtm: 1,
+                    waste_stm: 0,
+                    waste_mtm: 0,
+                },
+                Segment {
+                    start: 1,
+                    end: 2,
+                    stm: 1,
+                    mtm: 1,
+                    optimal_before_stm: 1,
+                    optimal_after_stm: 0,
+                    optimal_before_mtm: 1,
+                    optimal_after_mtm: 0,
+                    waste_stm: 0,
+                    waste_mtm: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_efficiency_waste() {
+        let mut stm_solver = Solver3x3Stm::new();
+        let mut mtm_solver = Solver3x3Mtm::new();
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/7 0 8").unwrap();
+        let solution = Algorithm::from_str("DULRL").unwrap();
+
+        let segments =
+            efficiency(&mut stm_solver, &mut mtm_solver, &scramble, &solution, &[2]).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].optimal_before_stm, 1);
+        assert_eq!(segments[0].optimal_after_stm, 1);
+        assert_eq!(segments[0].waste_stm, 2);
+        assert_eq!(segments[1].stm, 3);
+        assert_eq!(segments[1].waste_stm, 2);
+
+        let segments =
+            efficiency(&mut stm_solver, &mut mtm_solver, &scramble, &solution, &[]).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].mtm, 5);
+        assert_eq!(segments[0].waste_stm, 4);
+        assert_eq!(segments[0].waste_mtm, 4);
+    }
+
+    #[test]
+    fn test_efficiency_errors() {
+        let mut stm_solver = Solver3x3Stm::new();
+        let mut mtm_solver = Solver3x3Mtm::new();
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/0 7 8").unwrap();
+        let solution = Algorithm::from_str("L2").unwrap();
+
+        assert_eq!(
+            efficiency(&mut stm_solver, &mut mtm_solver, &scramble, &solution, &[3]),
+            Err(EfficiencyError::InvalidCheckpoint(3))
+        );
+        assert_eq!(
+            efficiency(
+                &mut stm_solver,
+                &mut mtm_solver,
+                &scramble,
+                &solution,
+                &[1, 1]
+            ),
+            Err(EfficiencyError::InvalidCheckpoint(1))
+        );
+        assert_eq!(
+            efficiency(
+                &mut stm_solver,
+                &mut mtm_solver,
+                &scramble,
+                &Algorithm::from_str("R").unwrap(),
+                &[]
+            ),
+            Err(EfficiencyError::IllegalAlgorithm)
+        );
+    }
+}
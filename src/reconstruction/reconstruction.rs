@@ -0,0 +1,406 @@
+//! Defines the [`Reconstruction`] type, which records a solve of a puzzle as a sequence of
+//! timestamped moves.
+
+use std::time::Duration;
+
+use num_traits::{AsPrimitive, PrimInt};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    algorithm::{
+        algorithm::Algorithm,
+        metric::{Mtm, SequenceMetric, Stm},
+        r#move::r#move::Move,
+    },
+    puzzle::{puzzle::Puzzle, sliding_puzzle::SlidingPuzzle as _},
+    reconstruction::splits::{splits, Milestone, Split},
+};
+
+/// Error type for [`Reconstruction`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReconstructionError {
+    /// Returned when a move can not be applied to the current state of the puzzle.
+    #[error("IllegalMove: move {mv} at index {index} can not be applied")]
+    IllegalMove {
+        /// The index of the move.
+        index: usize,
+        /// The move that could not be applied.
+        mv: Move,
+    },
+
+    /// Returned when the timestamp of a move is earlier than the timestamp of the previous move.
+    #[error(
+        "TimeDecreasing: move at index {index} has timestamp {time:?}, which is earlier than the \
+        previous timestamp {previous:?}"
+    )]
+    TimeDecreasing {
+        /// The index of the move.
+        index: usize,
+        /// The timestamp of the previous move.
+        previous: Duration,
+        /// The timestamp of the move.
+        time: Duration,
+    },
+}
+
+/// A [`Move`] together with the time at which it was made, measured from the start of the solve
+/// (i.e. the end of inspection).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimedMove {
+    /// The move.
+    pub mv: Move,
+    /// The time at which the move was made, measured from the start of the solve.
+    pub time: Duration,
+}
+
+impl TimedMove {
+    /// Creates a new [`TimedMove`].
+    #[must_use]
+    pub fn new(mv: Move, time: Duration) -> Self {
+        Self { mv, time }
+    }
+}
+
+/// The phases of a solve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Phase {
+    /// The inspection phase, before the solve starts.
+    Inspection,
+    /// The solve phase, between the end of inspection and the last move.
+    Solve,
+    /// After the last move.
+    Finished,
+}
+
+/// A solve of a puzzle, consisting of a scramble, an inspection time, and a sequence of
+/// [`TimedMove`]s applied to the scramble.
+///
+/// Timestamps of moves are measured from the end of inspection, and must be non-decreasing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "ReconstructionUnvalidated")
+)]
+pub struct Reconstruction {
+    scramble: Puzzle,
+    inspection: Duration,
+    moves: Vec<TimedMove>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    state: Puzzle,
+}
+
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+struct ReconstructionUnvalidated {
+    scramble: Puzzle,
+    inspection: Duration,
+    moves: Vec<TimedMove>,
+}
+
+impl TryFrom<ReconstructionUnvalidated> for Reconstruction {
+    type Error = ReconstructionError;
+
+    fn try_from(value: ReconstructionUnvalidated) -> Result<Self, Self::Error> {
+        let ReconstructionUnvalidated {
+            scramble,
+            inspection,
+            moves,
+        } = value;
+
+        Self::with_moves(scramble, inspection, moves)
+    }
+}
+
+impl Reconstruction {
+    /// Creates a new [`Reconstruction`] with no moves.
+    #[must_use]
+    pub fn new(scramble: Puzzle, inspection: Duration) -> Self {
+        Self {
+            state: scramble.clone(),
+            scramble,
+            inspection,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`Reconstruction`] from a list of moves.
+    pub fn with_moves(
+        scramble: Puzzle,
+        inspection: Duration,
+        moves: Vec<TimedMove>,
+    ) -> Result<Self, ReconstructionError> {
+        let mut reconstruction = Self::new(scramble, inspection);
+        reconstruction.moves.reserve(moves.len());
+
+        for TimedMove { mv, time } in moves {
+            reconstruction.push(mv, time)?;
+        }
+
+        Ok(reconstruction)
+    }
+
+    /// The scrambled puzzle.
+    #[must_use]
+    pub fn scramble(&self) -> &Puzzle {
+        &self.scramble
+    }
+
+    /// The duration of the inspection phase.
+    #[must_use]
+    pub fn inspection(&self) -> Duration {
+        self.inspection
+    }
+
+    /// The moves of the solve.
+    #[must_use]
+    pub fn moves(&self) -> &[TimedMove] {
+        &self.moves
+    }
+
+    /// The state of the puzzle after applying all moves to the scramble.
+    #[must_use]
+    pub fn state(&self) -> &Puzzle {
+        &self.state
+    }
+
+    /// Checks if the puzzle is solved after applying all moves to the scramble.
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.state.is_solved()
+    }
+
+    /// Appends a move made at time `time` (measured from the end of inspection).
+    pub fn push(&mut self, mv: Move, time: Duration) -> Result<(), ReconstructionError> {
+        let index = self.moves.len();
+
+        if let Some(previous) = self.moves.last().map(|m| m.time) {
+            if time < previous {
+                return Err(ReconstructionError::TimeDecreasing {
+                    index,
+                    previous,
+                    time,
+                });
+            }
+        }
+
+        if !self.state.try_apply_move(mv) {
+            return Err(ReconstructionError::IllegalMove { index, mv });
+        }
+
+        self.moves.push(TimedMove { mv, time });
+
+        Ok(())
+    }
+
+    /// Removes the last move and returns it, or `None` if there are no moves.
+    pub fn pop(&mut self) -> Option<TimedMove> {
+        let timed_move = self.moves.pop()?;
+        self.state.apply_move(timed_move.mv.inverse());
+        Some(timed_move)
+    }
+
+    /// The moves of the solve as an [`Algorithm`].
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.moves.iter().map(|m| m.mv).collect()
+    }
+
+    /// Splits the solve into steps, one for each milestone in `milestones`. See [`splits`].
+    #[must_use]
+    pub fn splits(&self, milestones: &[&dyn Milestone]) -> Vec<Split> {
+        // The moves of a reconstruction can always be applied to its scramble
+        splits(&self.scramble, &self.algorithm(), milestones).unwrap()
+    }
+
+    /// The state of the puzzle at time `time` (measured from the end of inspection), i.e. the
+    /// scramble with all moves made at or before `time` applied.
+    #[must_use]
+    pub fn state_at(&self, time: Duration) -> Puzzle {
+        let mut puzzle = self.scramble.clone();
+
+        for m in self.moves.iter().take_while(|m| m.time <= time) {
+            puzzle.apply_move(m.mv);
+        }
+
+        puzzle
+    }
+
+    /// The phase of the solve at time `time`, measured from the start of inspection.
+    #[must_use]
+    pub fn phase_at(&self, time: Duration) -> Phase {
+        if time < self.inspection {
+            Phase::Inspection
+        } else if time - self.inspection < self.time() {
+            Phase::Solve
+        } else {
+            Phase::Finished
+        }
+    }
+
+    /// The duration of the solve phase, i.e. the timestamp of the last move. Does not include
+    /// inspection.
+    #[must_use]
+    pub fn time(&self) -> Duration {
+        self.moves.last().map(|m| m.time).unwrap_or_default()
+    }
+
+    /// The total duration of the inspection and solve phases.
+    #[must_use]
+    pub fn total_time(&self) -> Duration {
+        self.inspection + self.time()
+    }
+
+    /// The length of the solve in the given [`SequenceMetric`].
+    #[must_use]
+    pub fn len<M, T>(&self) -> T
+    where
+        M: SequenceMetric,
+        T: PrimInt + 'static,
+        u64: AsPrimitive<T>,
+    {
+        M::len_moves(self.moves.iter().map(|m| m.mv))
+    }
+
+    /// The length of the solve in the [`Stm`] metric.
+    #[must_use]
+    pub fn len_stm<T>(&self) -> T
+    where
+        T: PrimInt + 'static,
+        u64: AsPrimitive<T>,
+    {
+        self.len::<Stm, T>()
+    }
+
+    /// The length of the solve in the [`Mtm`] metric.
+    #[must_use]
+    pub fn len_mtm<T>(&self) -> T
+    where
+        T: PrimInt + 'static,
+        u64: AsPrimitive<T>,
+    {
+        self.len::<Mtm, T>()
+    }
+
+    /// The number of moves per second in the given [`SequenceMetric`], or `None` if the solve
+    /// time is zero.
+    #[must_use]
+    pub fn tps<M: SequenceMetric>(&self) -> Option<f64> {
+        let time = self.time().as_secs_f64();
+        (time > 0.0).then(|| self.len::<M, u64>() as f64 / time)
+    }
+
+    /// The number of moves per second in the [`Stm`] metric, or `None` if the solve time is zero.
+    #[must_use]
+    pub fn tps_stm(&self) -> Option<f64> {
+        self.tps::<Stm>()
+    }
+
+    /// The number of moves per second in the [`Mtm`] metric, or `None` if the solve time is zero.
+    #[must_use]
+    pub fn tps_mtm(&self) -> Option<f64> {
+        self.tps::<Mtm>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::algorithm::direction::Direction;
+
+    fn reconstruction() -> Reconstruction {
+        let scramble = Puzzle::from_str("1 2 3/4 0 5/7 8 6").unwrap();
+        let moves = vec![
+            TimedMove::new(Move::new(Direction::Left, 1), Duration::from_millis(200)),
+            TimedMove::new(Move::new(Direction::Up, 1), Duration::from_millis(500)),
+        ];
+
+        Reconstruction::with_moves(scramble, Duration::from_secs(3), moves).unwrap()
+    }
+
+    #[test]
+    fn test_with_moves() {
+        let r = reconstruction();
+        assert!(r.is_solved());
+        assert_eq!(r.algorithm(), Algorithm::from_str("LU").unwrap());
+    }
+
+    #[test]
+    fn test_push() {
+        let mut r = Reconstruction::new(
+            Puzzle::from_str("1 2 3/4 0 5/7 8 6").unwrap(),
+            Duration::ZERO,
+        );
+
+        assert_eq!(
+            r.push(Move::new(Direction::Left, 2), Duration::from_millis(100)),
+            Err(ReconstructionError::IllegalMove {
+                index: 0,
+                mv: Move::new(Direction::Left, 2)
+            })
+        );
+        assert!(r
+            .push(Move::new(Direction::Up, 1), Duration::from_millis(100))
+            .is_ok());
+        assert_eq!(
+            r.push(Move::new(Direction::Down, 1), Duration::from_millis(50)),
+            Err(ReconstructionError::TimeDecreasing {
+                index: 1,
+                previous: Duration::from_millis(100),
+                time: Duration::from_millis(50),
+            })
+        );
+        assert_eq!(r.moves().len(), 1);
+        assert_eq!(r.state(), &Puzzle::from_str("1 2 3/4 8 5/7 0 6").unwrap());
+
+        assert_eq!(
+            r.pop(),
+            Some(TimedMove::new(
+                Move::new(Direction::Up, 1),
+                Duration::from_millis(100)
+            ))
+        );
+        assert_eq!(r.state(), r.scramble());
+    }
+
+    #[test]
+    fn test_time() {
+        let r = reconstruction();
+        assert_eq!(r.time(), Duration::from_millis(500));
+        assert_eq!(r.total_time(), Duration::from_millis(3500));
+        assert_eq!(r.phase_at(Duration::from_secs(1)), Phase::Inspection);
+        assert_eq!(r.phase_at(Duration::from_millis(3100)), Phase::Solve);
+        assert_eq!(r.phase_at(Duration::from_secs(4)), Phase::Finished);
+    }
+
+    #[test]
+    fn test_state_at() {
+        let r = reconstruction();
+        assert_eq!(r.state_at(Duration::ZERO), *r.scramble());
+        assert_eq!(
+            r.state_at(Duration::from_millis(200)),
+            Puzzle::from_str("1 2 3/4 5 0/7 8 6").unwrap()
+        );
+        assert!(r.state_at(Duration::from_secs(1)).is_solved());
+    }
+
+    #[test]
+    fn test_len_tps() {
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/0 7 8").unwrap();
+        let mut r = Reconstruction::new(scramble, Duration::ZERO);
+        assert_eq!(r.tps_stm(), None);
+
+        r.push(Move::new(Direction::Left, 2), Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(r.len_stm::<u64>(), 2);
+        assert_eq!(r.len_mtm::<u64>(), 1);
+        assert_eq!(r.tps_stm(), Some(4.0));
+        assert_eq!(r.tps_mtm(), Some(2.0));
+    }
+}
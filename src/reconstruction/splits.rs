@@ -0,0 +1,220 @@
+//! Defines functions for splitting a solution into steps, based on when a sequence of milestones
+//! (partially solved states) were completed.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    algorithm::{algorithm::Algorithm, metric::Mtm},
+    puzzle::{puzzle::Puzzle, sliding_puzzle::SlidingPuzzle as _, solved_state::SolvedState},
+};
+
+/// Error type for [`splits`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SplitsError {
+    /// Returned when the solution can not be applied to the scramble.
+    #[error("IllegalAlgorithm: the solution can not be applied to the scramble")]
+    IllegalAlgorithm,
+}
+
+/// A partially solved state of a [`Puzzle`], used to split a solution into steps.
+///
+/// This is an object safe version of [`SolvedState`], so that milestones of different types can be
+/// combined into a list. It is implemented for all types implementing [`SolvedState`], e.g.
+/// [`Partial`].
+///
+/// [`Partial`]: ../../puzzle/solved_state/struct.Partial.html
+pub trait Milestone {
+    /// Checks if the milestone is completed on `puzzle`.
+    #[must_use]
+    fn is_complete(&self, puzzle: &Puzzle) -> bool;
+}
+
+impl<S: SolvedState> Milestone for S {
+    fn is_complete(&self, puzzle: &Puzzle) -> bool {
+        self.is_solved(puzzle)
+    }
+}
+
+/// The result of splitting a solution at a milestone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Split {
+    /// The number of single tile moves after which the milestone became solved and stayed solved
+    /// until the end of the solution, or `None` if the milestone is not solved at the end of the
+    /// solution.
+    pub completed_at: Option<u64>,
+
+    /// The length of the step in [`Stm`], from the end of the previous completed step to
+    /// `completed_at`, or `None` if the milestone was not completed.
+    ///
+    /// [`Stm`]: ../../algorithm/metric/struct.Stm.html
+    pub stm: Option<u64>,
+
+    /// The length of the step in [`Mtm`], from the end of the previous completed step to
+    /// `completed_at`, or `None` if the milestone was not completed. A move that is split between
+    /// two steps counts as one move in both steps.
+    pub mtm: Option<u64>,
+}
+
+/// Splits `solution` into steps, one for each milestone in `milestones`, based on when each
+/// milestone was completed when `solution` is applied to `scramble`.
+///
+/// Each milestone is considered completed after the first single tile move from which it stays
+/// solved until the end of the solution. Each step starts where the previous completed step ended,
+/// so if a milestone is completed before the previous one, the length of its step is zero.
+pub fn splits(
+    scramble: &Puzzle,
+    solution: &Algorithm,
+    milestones: &[&dyn Milestone],
+) -> Result<Vec<Split>, SplitsError> {
+    if !scramble.can_apply_alg(solution) {
+        return Err(SplitsError::IllegalAlgorithm);
+    }
+
+    // Whether each milestone is complete after each number of single tile moves
+    let mut puzzle = scramble.clone();
+    let mut complete = vec![milestones
+        .iter()
+        .map(|m| m.is_complete(&puzzle))
+        .collect::<Vec<_>>()];
+
+    for dir in solution
+        .moves()
+        .iter()
+        .flat_map(|m| std::iter::repeat_n(m.direction, m.amount as usize))
+    {
+        puzzle.move_dir(dir);
+        complete.push(milestones.iter().map(|m| m.is_complete(&puzzle)).collect());
+    }
+
+    let mut start = 0;
+    let splits = (0..milestones.len())
+        .map(|i| {
+            let completed_at = complete.iter().rposition(|c| !c[i]).map_or(Some(0), |idx| {
+                (idx + 1 < complete.len()).then_some(idx as u64 + 1)
+            });
+
+            let Some(end) = completed_at else {
+                return Split {
+                    completed_at,
+                    stm: None,
+                    mtm: None,
+                };
+            };
+
+            let end = end.max(start);
+            let mtm = solution.try_slice(start..end).unwrap().len::<Mtm, u64>();
+            let stm = end - start;
+            start = end;
+
+            Split {
+                completed_at,
+                stm: Some(stm),
+                mtm: Some(mtm),
+            }
+        })
+        .collect();
+
+    Ok(splits)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr as _, time::Duration};
+
+    use super::*;
+    use crate::{
+        puzzle::{
+            label::label::{RowGrids, Rows},
+            solved_state::Partial,
+        },
+        reconstruction::reconstruction::Reconstruction,
+    };
+
+    #[test]
+    fn test_splits() {
+        let scramble = Puzzle::from_str("2 3 0/1 5 6/4 7 8").unwrap();
+        let solution = Algorithm::from_str("R2U2L2").unwrap();
+
+        let first_row = Partial::new(RowGrids, vec![0, 1, 2]);
+        let milestones: [&dyn Milestone; 2] = [&first_row, &RowGrids];
+
+        let s = splits(&scramble, &solution, &milestones).unwrap();
+        assert_eq!(
+            s,
+            vec![
+                Split {
+                    completed_at: Some(3),
+                    stm: Some(3),
+                    mtm: Some(2),
+                },
+                Split {
+                    completed_at: Some(6),
+                    stm: Some(3),
+                    mtm: Some(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_splits_stays_solved() {
+        // Both rows are solved at the start, then broken and solved again
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let solution = Algorithm::from_str("D2UU").unwrap();
+
+        let first_row = Partial::new(Rows, vec![0]);
+        let second_row = Partial::new(Rows, vec![1]);
+        let milestones: [&dyn Milestone; 2] = [&second_row, &first_row];
+
+        let s = splits(&scramble, &solution, &milestones).unwrap();
+        assert_eq!(s[0].completed_at, Some(4));
+        assert_eq!(s[0].stm, Some(4));
+        assert_eq!(s[0].mtm, Some(3));
+
+        // The first row was completed before the second row
+        assert_eq!(s[1].completed_at, Some(3));
+        assert_eq!(s[1].stm, Some(0));
+        assert_eq!(s[1].mtm, Some(0));
+    }
+
+    #[test]
+    fn test_splits_incomplete() {
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/7 8 0").unwrap();
+        let solution = Algorithm::from_str("D").unwrap();
+
+        let milestones: [&dyn Milestone; 1] = [&RowGrids];
+        let s = splits(&scramble, &solution, &milestones).unwrap();
+        assert_eq!(
+            s,
+            vec![Split {
+                completed_at: None,
+                stm: None,
+                mtm: None,
+            }]
+        );
+
+        let solution = Algorithm::from_str("R3").unwrap();
+        assert_eq!(
+            splits(&scramble, &solution, &milestones),
+            Err(SplitsError::IllegalAlgorithm)
+        );
+    }
+
+    #[test]
+    fn test_reconstruction_splits() {
+        let scramble = Puzzle::from_str("1 2 3/4 5 6/7 0 8").unwrap();
+        let mut r = Reconstruction::new(scramble, Duration::ZERO);
+        r.push(
+            Algorithm::from_str("L").unwrap().moves()[0],
+            Duration::from_millis(100),
+        )
+        .unwrap();
+
+        let milestones: [&dyn Milestone; 1] = [&RowGrids];
+        assert_eq!(r.splits(&milestones)[0].completed_at, Some(1));
+    }
+}
@@ -121,6 +121,8 @@ where
     }
 }
 
+// There is no solver for `GapTurn`: a single move has length 0 in that metric, so iterative
+// deepening on the length would never terminate at a given depth.
 impl<P, S, H> Solver<P, u8, S, H, Mtm> for GenericSolver<'_, P, S, H, Mtm>
 where
     P: SlidingPuzzle + Clone,
@@ -1,5 +1,7 @@
 //! Defines the [`Solver`] trait for a unified solver interface.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
@@ -10,6 +12,7 @@ use crate::{
 
 /// Error type for solvers.
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SolverError {
     /// Returned when the search finished without finding a solution.
     #[error("NoSolutionFound: no solution was found within the range searched")]